@@ -0,0 +1,38 @@
+extern crate ljmrs;
+
+use ljmrs::LJMLibrary;
+
+fn burst() {
+    #[cfg(feature = "dynlink")]
+    unsafe { LJMLibrary::init(None) }.unwrap();
+    #[cfg(feature = "staticlink")]
+    unsafe { LJMLibrary::init() }.unwrap();
+
+    let open_call = LJMLibrary::open_jack(
+        ljmrs::DeviceType::ANY,
+        ljmrs::ConnectionType::ANY,
+        "ANY".to_string(), // Stream is not supported on DEMO LabJacks
+    )
+    .expect("Could not open LabJack");
+
+    // AIN0 through AIN3
+    let addresses = vec![0, 2, 4, 6];
+
+    let burst = LJMLibrary::stream_burst(open_call, addresses, 100_000.0, 10_000)
+        .expect("Failed to collect burst");
+
+    println!(
+        "Collected {} scans at {} Hz",
+        burst.num_scans(),
+        burst.scan_rate
+    );
+
+    for (address, values) in burst.scan_list.iter().zip(burst.channels.iter()) {
+        let mean = values.iter().sum::<f64>() / values.len() as f64;
+        println!("Address {}: mean {:.4}V", address, mean);
+    }
+}
+
+fn main() {
+    burst();
+}
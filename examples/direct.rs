@@ -28,6 +28,15 @@ fn stream() {
 
     let elapsed = now.elapsed();
     println!("Elapsed: {:.2?}", elapsed);
+
+    let now = Instant::now();
+
+    for _ in 0..50 {
+        LJMLibrary::read_names(open_call, &["AIN0", "AIN1"]).expect("");
+    }
+
+    let elapsed = now.elapsed();
+    println!("Elapsed (batched): {:.2?}", elapsed);
 }
 
 fn main() {
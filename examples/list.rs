@@ -0,0 +1,32 @@
+extern crate ljmrs;
+
+use ljmrs::LJMLibrary;
+
+fn list() {
+    #[cfg(feature = "dynlink")]
+    unsafe { LJMLibrary::init(None) }.unwrap();
+    #[cfg(feature = "staticlink")]
+    unsafe { LJMLibrary::init() }.unwrap();
+
+    let devices = LJMLibrary::list_all_extended(
+        ljmrs::DeviceType::ANY,
+        ljmrs::ConnectionType::ANY,
+        vec!["DEVICE_NAME_DEFAULT", "FIRMWARE_VERSION"],
+    )
+    .expect("Could not list devices");
+
+    println!("Found {} devices", devices.len());
+
+    for device in devices {
+        let name = device
+            .register("DEVICE_NAME_DEFAULT")
+            .and_then(|r| r.as_string());
+        let firmware = device.register("FIRMWARE_VERSION").and_then(|r| r.as_f64());
+
+        println!("{} ({:?}, firmware {:?})", device.device, name, firmware);
+    }
+}
+
+fn main() {
+    list();
+}
@@ -24,7 +24,7 @@ fn stream() {
     // Do note that this examples will fail on
     // DEMO mode labjacks, as currently stream mode
     // is not supported for them.
-    LJMLibrary::stream_start(open_call, 2, 50_000.0, streams).expect("Failed to start stream");
+    LJMLibrary::stream_start_addr(open_call, 2, 50_000.0, streams).expect("Failed to start stream");
 
     assert!(LJMLibrary::is_stream_active(open_call));
 
@@ -32,9 +32,16 @@ fn stream() {
 
     let mut i = 0;
     while i < 50 {
-        let read_value = LJMLibrary::stream_read(open_call).expect("Could not read values");
-
-        println!("Got {}: {:?}", read_value.len(), read_value);
+        let frame = LJMLibrary::stream_read_frame(open_call).expect("Could not read values");
+
+        for channel in frame.channels() {
+            println!(
+                "Got {} from {}: {:?}",
+                channel.len(),
+                channel.name,
+                channel.to_vec()
+            );
+        }
         i += 1;
     }
 
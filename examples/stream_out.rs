@@ -0,0 +1,41 @@
+extern crate ljmrs;
+
+use ljmrs::{LJMLibrary, Waveform};
+
+fn stream_out() {
+    #[cfg(feature = "dynlink")]
+    unsafe { LJMLibrary::init(None) }.unwrap();
+    #[cfg(feature = "staticlink")]
+    unsafe { LJMLibrary::init() }.unwrap();
+
+    let open_call = LJMLibrary::open_jack(
+        ljmrs::DeviceType::ANY,
+        ljmrs::ConnectionType::ANY,
+        "ANY".to_string(), // Stream is not supported on DEMO LabJacks
+    )
+    .expect("Could not open LabJack");
+
+    let scan_rate = 1_000.0;
+
+    // A 10Hz sine wave between 1V and 4V on DAC0
+    let points = Waveform::samples_for(scan_rate, 10.0);
+    let sine = Waveform::Sine.generate(points, 1.5, 2.5);
+
+    LJMLibrary::periodic_stream_out(open_call, 0, "DAC0", scan_rate, &sine)
+        .expect("Failed to configure stream-out");
+
+    // STREAM_OUT0 is appended to the scan list
+    LJMLibrary::stream_start_addr(open_call, 500, scan_rate, vec!["AIN0"])
+        .expect("Failed to start stream");
+
+    for _ in 0..10 {
+        let read_value = LJMLibrary::stream_read(open_call).expect("Could not read values");
+        println!("Got {} values", read_value.len());
+    }
+
+    LJMLibrary::stream_stop(open_call).expect("Expected Value");
+}
+
+fn main() {
+    stream_out();
+}
@@ -31,6 +31,12 @@ fn read() {
 
     LJMLibrary::write_name(open_call, "DAC0", 2.5).expect("Expected Value");
 
+    LJMLibrary::write_names(
+        open_call,
+        &[("AIN0_RANGE", 10.0), ("AIN0_RESOLUTION_INDEX", 0.0)],
+    )
+    .expect("Expected Value");
+
     let now = Instant::now();
 
     let read_value = LJMLibrary::read_name(open_call, "TEST_INT32").expect("Expected Value");
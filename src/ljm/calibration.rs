@@ -0,0 +1,148 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{DeviceType, LJMError, LJMLibrary};
+
+// Where the T7's calibration constants are stored in its internal flash,
+// beginning with those of the high speed AIN converter used when streaming.
+const T7_CALIBRATION_ADDRESS: f64 = 0x3C4000 as f64;
+
+// The T7's AIN ranges, in the order of their constants in flash.
+const T7_RANGES: [f64; 4] = [10.0, 1.0, 0.1, 0.01];
+
+// Each range has a PSlope, NSlope, Center and Offset.
+const CONSTANTS_PER_RANGE: usize = 4;
+
+// The number of 32-bit words read from flash at a time.
+const FLASH_READ_WORDS: usize = 8;
+
+/// The calibration constants of an AIN range, converting
+/// the raw counts of a binary stream into volts.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AinCalibration {
+    /// The positive bound of the range in volts, such as `10.0` for ±10V.
+    pub range: f64,
+    /// Volts per count above `center`.
+    pub positive_slope: f64,
+    /// Volts per count below `center`, which is negative.
+    pub negative_slope: f64,
+    /// The counts read at 0V.
+    pub center: f64,
+    /// The offset in volts, which is not needed for stream data.
+    pub offset: f64,
+}
+
+impl AinCalibration {
+    pub fn apply(&self, counts: u16) -> f64 {
+        let counts = counts as f64;
+
+        if counts < self.center {
+            (self.center - counts) * self.negative_slope
+        } else {
+            (counts - self.center) * self.positive_slope
+        }
+    }
+}
+
+/// A device's AIN calibration constants for each range, as read when a
+/// binary stream is started (see `StreamConfig::binary`), such that raw
+/// counts from `LJMLibrary::stream_read_raw` may be kept and converted later.
+///
+/// ```rust,ignore
+/// let calibration = LJMLibrary::stream_calibration(handle)?.unwrap();
+/// let counts = LJMLibrary::stream_read_raw(handle)?;
+///
+/// let volts = calibration.apply(10.0, counts[0]);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CalibrationTable {
+    ranges: Vec<AinCalibration>,
+}
+
+impl CalibrationTable {
+    pub fn new(ranges: Vec<AinCalibration>) -> Self {
+        CalibrationTable { ranges }
+    }
+
+    /// Parses the high speed AIN constants of a T7, as stored in flash:
+    /// the PSlope, NSlope, Center and Offset of each range from ±10V to ±0.01V.
+    pub fn from_t7_constants(constants: &[f32]) -> Result<Self, LJMError> {
+        if constants.len() != T7_RANGES.len() * CONSTANTS_PER_RANGE {
+            return Err(LJMError::InvalidCalibration(format!(
+                "expected {} constants, got {}",
+                T7_RANGES.len() * CONSTANTS_PER_RANGE,
+                constants.len()
+            )));
+        }
+
+        let ranges = T7_RANGES
+            .iter()
+            .zip(constants.chunks_exact(CONSTANTS_PER_RANGE))
+            .map(|(range, constants)| {
+                // Unprogrammed flash reads as NaN.
+                if constants.iter().any(|constant| !constant.is_finite()) {
+                    return Err(LJMError::InvalidCalibration(format!(
+                        "constants for the ±{}V range are not set",
+                        range
+                    )));
+                }
+
+                Ok(AinCalibration {
+                    range: *range,
+                    positive_slope: constants[0] as f64,
+                    negative_slope: constants[1] as f64,
+                    center: constants[2] as f64,
+                    offset: constants[3] as f64,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(CalibrationTable { ranges })
+    }
+
+    /// Reads the AIN calibration constants from the device's flash.
+    /// Only T7 devices are supported.
+    pub fn read(handle: i32) -> Result<Self, LJMError> {
+        let device_type = LJMLibrary::get_handle_info(handle)?.device_type;
+        if !matches!(device_type, DeviceType::T7) {
+            return Err(LJMError::InvalidCalibration(format!(
+                "calibration constants cannot be read from {:?} devices",
+                device_type
+            )));
+        }
+
+        let mut constants = Vec::with_capacity(T7_RANGES.len() * CONSTANTS_PER_RANGE);
+        while constants.len() < constants.capacity() {
+            let pointer = T7_CALIBRATION_ADDRESS + (constants.len() * 4) as f64;
+            LJMLibrary::write_name(handle, "INTERNAL_FLASH_READ_POINTER", pointer)?;
+
+            let words =
+                LJMLibrary::read_name_array(handle, "INTERNAL_FLASH_READ", FLASH_READ_WORDS)?;
+            constants.extend(words.iter().map(|word| f32::from_bits(*word as u32)));
+        }
+
+        CalibrationTable::from_t7_constants(&constants)
+    }
+
+    pub fn ranges(&self) -> &[AinCalibration] {
+        &self.ranges
+    }
+
+    /// The constants for the range with the given positive bound in volts.
+    pub fn get(&self, range: f64) -> Option<&AinCalibration> {
+        self.ranges
+            .iter()
+            .find(|calibration| calibration.range == range)
+    }
+
+    /// Converts raw `counts` read with the given range into volts.
+    ///
+    /// Returns an `Option` rather than bare volts, being `None` if the
+    /// table has no constants for the range (such as `5.0`, which is not
+    /// a range of the device).
+    pub fn apply(&self, range: f64, counts: u16) -> Option<f64> {
+        self.get(range).map(|calibration| calibration.apply(counts))
+    }
+}
@@ -1,13 +1,18 @@
 #[cfg(feature = "stream")]
 use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
+use std::net::Ipv4Addr;
 use std::sync::OnceLock;
+#[cfg(feature = "stream")]
+use std::{
+    ffi::c_void,
+    fmt::Display,
+    sync::{Arc, Mutex, RwLock},
+};
 use std::{
     ffi::{c_char, c_uint, CString},
     os::raw::c_double,
 };
-#[cfg(feature = "stream")]
-use std::{fmt::Display, sync::RwLock};
 
 #[cfg(feature = "dynlink")]
 use libloading::{Library, Symbol};
@@ -16,17 +21,109 @@ use libloading::{Library, Symbol};
 use crate::lib;
 
 use crate::{
-    ljm::handle::{ConnectionType, DeviceHandleInfo, DeviceType},
-    LJMError,
+    ljm::handle::{
+        ConnectionType, DeviceHandleInfo, DeviceType, DiscoveredDevice, DiscoveredRegister,
+        ExtendedDiscoveredDevice,
+    },
+    ljm::transaction::{Target, Transaction, TransactionResult},
+    ErrorFrame, LJMError,
 };
 
 #[cfg(feature = "stream")]
-use crate::ljm::stream::LJMStream;
+use crate::ljm::calibration::CalibrationTable;
+#[cfg(feature = "stream")]
+use crate::ljm::replay::Replay;
+#[cfg(feature = "stream")]
+use crate::ljm::stream::{
+    deinterleave, Burst, Capture, CaptureLayout, LJMStream, StreamBacklog, StreamBlock,
+    StreamCallback, StreamClock, StreamFrame, StreamStatus, StreamTiming,
+    STREAM_DATA_CAPTURE_16_ADDRESS,
+};
+#[cfg(all(feature = "stream", feature = "tokio"))]
+use crate::ljm::stream_async::AsyncStream;
+#[cfg(feature = "stream")]
+use crate::ljm::stream_config::{is_ain_channel, StreamConfig};
 #[cfg(feature = "lua")]
 use crate::lua::LJMLua;
+#[cfg(feature = "stream")]
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
 
 static LJM_WRAPPER: OnceLock<LJMLibrary> = OnceLock::new();
 
+#[cfg(feature = "stream")]
+type LJMStreamReadCallback = Option<unsafe extern "C" fn(*mut c_void)>;
+
+/// Invoked by LJM's stream thread once `scans_per_read` scans are ready.
+///
+/// # Safety
+/// `arg` must point to the `StreamCallback` held by the handle's `LJMStream`,
+/// which is kept alive until the callback is cleared in `stream_stop`.
+#[cfg(feature = "stream")]
+unsafe extern "C" fn stream_read_callback(arg: *mut c_void) {
+    let context = unsafe { &*(arg as *const StreamCallback) };
+
+    let mut data = vec![0.0; context.scans_per_read * context.num_channels];
+    let read = if context.layout.is_empty() {
+        LJMLibrary::e_stream_read(context.handle, &mut data)
+    } else {
+        let mut physical = vec![0.0; context.scans_per_read * context.layout.physical_len()];
+        let read = LJMLibrary::e_stream_read(context.handle, &mut physical);
+        context.layout.combine(&physical, &mut data);
+        read
+    };
+    let Ok((dev_scan_backlog, ljm_scan_backlog)) = read else {
+        return;
+    };
+
+    context.backlog.record(dev_scan_backlog, ljm_scan_backlog);
+    let timing = context.clock.advance(&data, true);
+
+    if let Ok(mut callback) = context.callback.lock() {
+        // Unwinding into LJM's thread would abort the process.
+        let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            callback(StreamBlock::new(
+                data,
+                context.num_channels,
+                dev_scan_backlog,
+                ljm_scan_backlog,
+                Some(timing),
+            ))
+        }));
+    }
+}
+
+// The address of `STREAM_OUT0`, with each subsequent stream-out following it.
+#[cfg(feature = "stream")]
+const STREAM_OUT_ADDRESS: i32 = 4800;
+#[cfg(feature = "stream")]
+const NUM_STREAM_OUTS: i32 = 4;
+
+/// Virtual handles for replays are allocated downwards from this value,
+/// so as not to collide with the handles LJM allocates.
+#[cfg(feature = "stream")]
+pub const REPLAY_HANDLE_BASE: i32 = -1_000_000;
+#[cfg(feature = "stream")]
+static NEXT_REPLAY_HANDLE: AtomicI32 = AtomicI32::new(REPLAY_HANDLE_BASE);
+
+// Returned by `LJM_eStreamRead` with `LJM_STREAM_SCANS_RETURN` set to
+// `LJM_STREAM_SCANS_RETURN_ALL_OR_NONE` when fewer than `scans_per_read`
+// scans have been received.
+#[cfg(feature = "stream")]
+const LJME_NO_SCANS_RETURNED: i32 = 1309;
+
+#[cfg(feature = "stream")]
+const LJM_STREAM_AIN_BINARY: &str = "LJM_STREAM_AIN_BINARY";
+
+// Reported as the error address of multi-frame calls when no frame caused the error.
+const LJM_INVALID_NAME_ADDRESS: i32 = -1;
+
+// The maximum number of devices `LJM_ListAll` will report.
+const LJM_LIST_ALL_SIZE: usize = 128;
+
+/// The maximum length of a `LJM_STRING` register, excluding the null terminator.
+pub const LJM_STRING_MAX_SIZE: usize = 49;
+const LJM_STRING_ALLOCATION_SIZE: usize = LJM_STRING_MAX_SIZE + 1;
+
 pub struct LJMLibrary {
     #[cfg(feature = "dynlink")]
     pub library: Option<Library>,
@@ -34,6 +131,15 @@ pub struct LJMLibrary {
     #[cfg(feature = "stream")]
     stream: RwLock<HashMap<i32, LJMStream>>,
 
+    // Stream-out addresses configured for a handle, which are
+    // added to the scan list when its stream is next started.
+    #[cfg(feature = "stream")]
+    stream_out: RwLock<HashMap<i32, Vec<i32>>>,
+
+    // Recordings opened as virtual handles with `open_replay`.
+    #[cfg(feature = "stream")]
+    replay: RwLock<HashMap<i32, Arc<Mutex<Replay>>>>,
+
     // A device can only have one module at a time.
     #[cfg(feature = "lua")]
     module: RwLock<Option<LJMLua>>,
@@ -51,12 +157,74 @@ impl LJMLibrary {
             return Err(LJMError::ErrorCode(
                 error_code.into(),
                 LJMLibrary::error_to_string(error_code)?,
+                None,
+            ));
+        }
+
+        Ok(value)
+    }
+
+    /// As `error_code`, but for multi-frame calls which report the
+    /// device address that caused the error. The address is matched
+    /// against `frames` to find the failing frame.
+    pub(crate) fn error_code_at<T>(
+        value: T,
+        error_code: i32,
+        error_address: i32,
+        frames: impl IntoIterator<Item = Option<i32>>,
+    ) -> Result<T, LJMError> {
+        if error_code != 0 {
+            // LJM reports `LJM_INVALID_NAME_ADDRESS` when no frame caused the error.
+            let frame = (error_address != LJM_INVALID_NAME_ADDRESS).then(|| ErrorFrame {
+                address: error_address,
+                index: frames
+                    .into_iter()
+                    .position(|address| address == Some(error_address)),
+            });
+
+            return Err(LJMError::ErrorCode(
+                error_code.into(),
+                LJMLibrary::error_to_string(error_code)?,
+                frame,
             ));
         }
 
         Ok(value)
     }
 
+    // Resolves the addresses of named frames, only used to
+    // locate the failing frame when reporting an error.
+    fn frame_addresses(names: &[CString]) -> Vec<Option<i32>> {
+        names
+            .iter()
+            .map(|name| {
+                name.to_str()
+                    .ok()
+                    .and_then(|name| LJMLibrary::name_to_address(name).ok())
+                    .map(|(address, _)| address)
+            })
+            .collect()
+    }
+
+    // The address of each value of an array call, only used to
+    // locate the failing value when reporting an error.
+    fn array_frames(address: i32, data_type: i32, size: usize) -> Vec<Option<i32>> {
+        let registers = DiscoveredRegister::registers_for_type(data_type).unwrap_or(1);
+
+        (0..size as i32)
+            .map(|index| Some(address + index * registers))
+            .collect()
+    }
+
+    // As `array_frames`, for an array starting at a named register.
+    fn name_array_frames(name: &CString, size: usize) -> Vec<Option<i32>> {
+        name.to_str()
+            .ok()
+            .and_then(|name| LJMLibrary::name_to_address(name).ok())
+            .map(|(address, data_type)| LJMLibrary::array_frames(address, data_type, size))
+            .unwrap_or_default()
+    }
+
     pub fn get_library_path() -> String {
         let os = std::env::consts::OS;
 
@@ -82,7 +250,7 @@ impl LJMLibrary {
     }
 
     #[cfg(feature = "dynlink")]
-    unsafe fn get_c_function<T>(name: &[u8]) -> Result<Symbol<T>, LJMError> {
+    unsafe fn get_c_function<T>(name: &[u8]) -> Result<Symbol<'_, T>, LJMError> {
         let library = LJMLibrary::get_library()?;
 
         match library.get::<T>(name) {
@@ -124,10 +292,14 @@ impl LJMLibrary {
                 library: Some(library),
                 #[cfg(feature = "stream")]
                 stream: RwLock::new(HashMap::new()),
+                #[cfg(feature = "stream")]
+                stream_out: RwLock::new(HashMap::new()),
+                #[cfg(feature = "stream")]
+                replay: RwLock::new(HashMap::new()),
                 #[cfg(feature = "lua")]
                 module: RwLock::new(None),
             })
-            .map_err(LJMError::WrapperInvalid)
+            .map_err(|wrapper| LJMError::WrapperInvalid(Box::new(wrapper)))
     }
 
     #[cfg(all(feature = "staticlink", not(feature = "dynlink")))]
@@ -136,10 +308,32 @@ impl LJMLibrary {
             .set(LJMLibrary {
                 #[cfg(feature = "stream")]
                 stream: RwLock::new(HashMap::new()),
+                #[cfg(feature = "stream")]
+                stream_out: RwLock::new(HashMap::new()),
+                #[cfg(feature = "stream")]
+                replay: RwLock::new(HashMap::new()),
+                #[cfg(feature = "lua")]
+                module: RwLock::new(None),
+            })
+            .map_err(|wrapper| LJMError::WrapperInvalid(Box::new(wrapper)))
+    }
+
+    /// Initializes the wrapper without loading LJM, such that only virtual
+    /// handles opened with `open_replay` may be used, as when testing stream
+    /// processing on machines without LJM installed.
+    #[cfg(feature = "stream")]
+    pub fn init_replay() -> Result<(), LJMError> {
+        LJM_WRAPPER
+            .set(LJMLibrary {
+                #[cfg(feature = "dynlink")]
+                library: None,
+                stream: RwLock::new(HashMap::new()),
+                stream_out: RwLock::new(HashMap::new()),
+                replay: RwLock::new(HashMap::new()),
                 #[cfg(feature = "lua")]
                 module: RwLock::new(None),
             })
-            .map_err(LJMError::WrapperInvalid)
+            .map_err(|wrapper| LJMError::WrapperInvalid(Box::new(wrapper)))
     }
 
     #[doc(alias = "LJM_ErrorToString")]
@@ -238,393 +432,1790 @@ impl LJMLibrary {
         LJMLibrary::error_code((), error_code)
     }
 
-    #[doc(alias = "LJM_eWriteNameByteArray")]
-    pub fn write_name_byte_array<T: Into<Vec<u8>>, B: Into<Vec<u8>>>(
+    /// Writes multiple registers by name in as few packets as possible.
+    /// Takes `(name, value)` pairs, which are written in order.
+    ///
+    /// If a register fails, the `ErrorFrame` of the `LJMError::ErrorCode` identifies
+    /// the index of the failing pair.
+    #[doc(alias = "LJM_eWriteNames")]
+    pub fn write_names<T: ToString, V: Into<c_double> + Copy>(
         handle: i32,
-        name_to_write: T,
-        size: i32,
-        bytes: B,
+        values_to_write: &[(T, V)],
     ) -> Result<(), LJMError> {
         #[cfg(feature = "dynlink")]
-        let d_write_name_byte_array: Symbol<
-            extern "C" fn(i32, *const c_char, i32, *const c_char, *mut i32) -> i32,
-        > = unsafe { LJMLibrary::get_c_function(b"LJM_eWriteNameByteArray")? };
+        let d_write_names: Symbol<
+            extern "C" fn(i32, i32, *const *const c_char, *const c_double, *mut i32) -> i32,
+        > = unsafe { LJMLibrary::get_c_function(b"LJM_eWriteNames")? };
+
+        let names = values_to_write
+            .iter()
+            .map(|(name, _)| CString::new(name.to_string()))
+            .collect::<Result<Vec<CString>, _>>()
+            .map_err(|_| LJMError::CStringConversionFailed)?;
+        let mut name_ptrs: Vec<*const c_char> = names.iter().map(|name| name.as_ptr()).collect();
 
-        let btw = CString::new(bytes).map_err(|_| LJMError::CStringConversionFailed)?; // Bytes-To-Write
-        let ntw = CString::new(name_to_write).map_err(|_| LJMError::CStringConversionFailed)?; // Name-To-Write
+        let values: Vec<c_double> = values_to_write
+            .iter()
+            .map(|(_, value)| (*value).into())
+            .collect();
+        let mut error_addr: i32 = -1;
 
-        let mut error_addr: i32 = 0;
         #[cfg(feature = "dynlink")]
-        let error_code =
-            d_write_name_byte_array(handle, ntw.as_ptr(), size, btw.as_ptr(), &mut error_addr);
+        let error_code = d_write_names(
+            handle,
+            names.len() as i32,
+            name_ptrs.as_mut_ptr(),
+            values.as_ptr(),
+            &mut error_addr,
+        );
         #[cfg(feature = "staticlink")]
         let error_code = unsafe {
-            lib::LJM_eWriteNameByteArray(handle, ntw.as_ptr(), size, btw.as_ptr(), &mut error_addr)
+            lib::LJM_eWriteNames(
+                handle,
+                names.len() as i32,
+                name_ptrs.as_mut_ptr(),
+                values.as_ptr(),
+                &mut error_addr,
+            )
         };
 
-        LJMLibrary::error_code((), error_code)
+        if error_code != 0 {
+            let frames = LJMLibrary::frame_addresses(&names);
+            return LJMLibrary::error_code_at((), error_code, error_addr, frames);
+        }
+
+        Ok(())
     }
 
-    #[doc(alias = "LJM_eReadNameByteArray")]
-    pub fn read_name_byte_array<T: Into<Vec<u8>>>(
+    /// Writes multiple registers given as `(address, data_type, value)`
+    /// in as few packets as possible, in order.
+    ///
+    /// If a register fails, the `ErrorFrame` of the `LJMError::ErrorCode` identifies
+    /// the index of the failing register.
+    #[doc(alias = "LJM_eWriteAddresses")]
+    pub fn write_addrs<V: Into<c_double> + Copy>(
         handle: i32,
-        name_to_read: T,
-        size: i32,
-    ) -> Result<Vec<u8>, LJMError> {
-        #[cfg(feature = "dynlink")]
-        let d_write_name_byte_array: Symbol<
-            extern "C" fn(i32, *const c_char, i32, *mut u8, *mut i32) -> i32,
-        > = unsafe { LJMLibrary::get_c_function(b"LJM_eReadNameByteArray")? };
-
-        // Only allocate as much as we need, and do so such that
-        // it will be initialized when written to - the C lib
-        // does not know the context, so we'll Box it (heap-alloc)
-        // with a static size of `size`.
-        #[cfg(feature = "staticlink")]
-        let mut buffer: Box<[c_char]> = vec![c_char::from(0); size as usize].into_boxed_slice();
+        values_to_write: &[(i32, i32, V)],
+    ) -> Result<(), LJMError> {
         #[cfg(feature = "dynlink")]
-        let mut buffer: Box<[u8]> = vec![0u8; size as usize].into_boxed_slice();
+        let d_write_addrs: Symbol<
+            extern "C" fn(i32, i32, *const i32, *const i32, *const c_double, *mut i32) -> i32,
+        > = unsafe { LJMLibrary::get_c_function(b"LJM_eWriteAddresses")? };
+
+        let addrs: Vec<i32> = values_to_write.iter().map(|(a, _, _)| *a).collect();
+        let types: Vec<i32> = values_to_write.iter().map(|(_, t, _)| *t).collect();
+        let values: Vec<c_double> = values_to_write
+            .iter()
+            .map(|(_, _, value)| (*value).into())
+            .collect();
+        let mut error_addr: i32 = -1;
 
-        let ntr = CString::new(name_to_read).map_err(|_| LJMError::CStringConversionFailed)?; // Name-To-Write
-
-        let mut error_addr: i32 = 0;
         #[cfg(feature = "dynlink")]
-        let error_code = d_write_name_byte_array(
+        let error_code = d_write_addrs(
             handle,
-            ntr.as_ptr(),
-            size,
-            buffer.as_mut_ptr(),
+            addrs.len() as i32,
+            addrs.as_ptr(),
+            types.as_ptr(),
+            values.as_ptr(),
             &mut error_addr,
         );
         #[cfg(feature = "staticlink")]
         let error_code = unsafe {
-            lib::LJM_eWriteNameByteArray(
+            lib::LJM_eWriteAddresses(
                 handle,
-                ntr.as_ptr(),
-                size,
-                buffer.as_mut_ptr(),
+                addrs.len() as i32,
+                addrs.as_ptr(),
+                types.as_ptr(),
+                values.as_ptr(),
                 &mut error_addr,
             )
         };
 
-        #[cfg(feature = "staticlink")]
-        let as_vec = buffer.to_vec().into_iter().map(|v| v as u8).collect();
+        LJMLibrary::error_code_at((), error_code, error_addr, addrs.iter().copied().map(Some))
+    }
 
-        #[cfg(feature = "dynlink")]
-        let as_vec = buffer.into_vec();
+    /// Executes a `Transaction` of mixed reads and writes as a single
+    /// LJM call, returning the results of each step in order.
+    ///
+    /// If a frame fails, the `ErrorFrame` of the `LJMError::ErrorCode` identifies
+    /// the index of the failing step.
+    #[doc(alias = "LJM_eNames")]
+    #[doc(alias = "LJM_eAddresses")]
+    pub fn transaction(
+        handle: i32,
+        transaction: &Transaction,
+    ) -> Result<TransactionResult, LJMError> {
+        let (writes, num_values, mut values) = transaction.compile();
+        let mut error_addr: i32 = -1;
+
+        let named = transaction
+            .steps
+            .iter()
+            .map(|step| match step.target() {
+                Target::Name(name) => Some(name),
+                Target::Address(_, _) => None,
+            })
+            .collect::<Option<Vec<&String>>>();
+
+        if let Some(named) = named {
+            #[cfg(feature = "dynlink")]
+            let d_e_names: Symbol<
+                extern "C" fn(
+                    i32,
+                    i32,
+                    *const *const c_char,
+                    *const i32,
+                    *const i32,
+                    *mut c_double,
+                    *mut i32,
+                ) -> i32,
+            > = unsafe { LJMLibrary::get_c_function(b"LJM_eNames")? };
+
+            let names = named
+                .iter()
+                .map(|name| CString::new(name.as_str()))
+                .collect::<Result<Vec<CString>, _>>()
+                .map_err(|_| LJMError::CStringConversionFailed)?;
+            let mut name_ptrs: Vec<*const c_char> =
+                names.iter().map(|name| name.as_ptr()).collect();
+
+            #[cfg(feature = "dynlink")]
+            let error_code = d_e_names(
+                handle,
+                names.len() as i32,
+                name_ptrs.as_mut_ptr(),
+                writes.as_ptr(),
+                num_values.as_ptr(),
+                values.as_mut_ptr(),
+                &mut error_addr,
+            );
+            #[cfg(feature = "staticlink")]
+            let error_code = unsafe {
+                lib::LJM_eNames(
+                    handle,
+                    names.len() as i32,
+                    name_ptrs.as_mut_ptr(),
+                    writes.as_ptr(),
+                    num_values.as_ptr(),
+                    values.as_mut_ptr(),
+                    &mut error_addr,
+                )
+            };
+
+            if error_code != 0 {
+                let frames = LJMLibrary::frame_addresses(&names);
+                return LJMLibrary::error_code_at(
+                    transaction.results(&values),
+                    error_code,
+                    error_addr,
+                    frames,
+                );
+            }
 
-        LJMLibrary::error_code(as_vec, error_code)
-    }
+            return Ok(transaction.results(&values));
+        }
 
-    /// Reads from a labjack given the handle and name to read.
-    /// Returns an f64 value that is read from the labjack.
-    #[doc(alias = "LJM_eReadName")]
-    pub fn read_name<T: Into<Vec<u8>>>(handle: i32, name_to_read: T) -> Result<f64, LJMError> {
         #[cfg(feature = "dynlink")]
-        let d_read_from_aadr: Symbol<
-            extern "C" fn(i32, *const c_char, *mut c_double) -> i32,
-        > = unsafe { LJMLibrary::get_c_function(b"LJM_eReadName")? };
-
-        let ntr = CString::new(name_to_read).map_err(|_| LJMError::CStringConversionFailed)?;
-        let mut vtr = c_double::from(-1);
+        let d_e_addresses: Symbol<
+            extern "C" fn(
+                i32,
+                i32,
+                *const i32,
+                *const i32,
+                *const i32,
+                *const i32,
+                *mut c_double,
+                *mut i32,
+            ) -> i32,
+        > = unsafe { LJMLibrary::get_c_function(b"LJM_eAddresses")? };
+
+        let (addrs, types): (Vec<i32>, Vec<i32>) = transaction
+            .steps
+            .iter()
+            .map(|step| match step.target() {
+                Target::Name(name) => LJMLibrary::name_to_address(name),
+                Target::Address(address, data_type) => Ok((*address, *data_type)),
+            })
+            .collect::<Result<Vec<(i32, i32)>, LJMError>>()?
+            .into_iter()
+            .unzip();
 
         #[cfg(feature = "dynlink")]
-        let error_code = d_read_from_aadr(handle, ntr.as_ptr(), &mut vtr);
+        let error_code = d_e_addresses(
+            handle,
+            addrs.len() as i32,
+            addrs.as_ptr(),
+            types.as_ptr(),
+            writes.as_ptr(),
+            num_values.as_ptr(),
+            values.as_mut_ptr(),
+            &mut error_addr,
+        );
         #[cfg(feature = "staticlink")]
-        let error_code = unsafe { lib::LJM_eReadName(handle, ntr.as_ptr(), &mut vtr) };
+        let error_code = unsafe {
+            lib::LJM_eAddresses(
+                handle,
+                addrs.len() as i32,
+                addrs.as_ptr(),
+                types.as_ptr(),
+                writes.as_ptr(),
+                num_values.as_ptr(),
+                values.as_mut_ptr(),
+                &mut error_addr,
+            )
+        };
 
-        LJMLibrary::error_code(vtr, error_code)
+        LJMLibrary::error_code_at(
+            transaction.results(&values),
+            error_code,
+            error_addr,
+            addrs.iter().copied().map(Some),
+        )
     }
 
-    #[doc(alias = "LJM_eReadName")]
-    pub fn read_addr(handle: i32, addr: i32, data_type: i32) -> Result<f64, LJMError> {
+    /// Writes consecutive values starting at the named register, such as
+    /// `USER_RAM0_F32` onwards, or repeatedly to a buffer register
+    /// such as `STREAM_OUT0_BUFFER_F32`.
+    #[doc(alias = "LJM_eWriteNameArray")]
+    pub fn write_name_array<T: Into<Vec<u8>>>(
+        handle: i32,
+        name_to_write: T,
+        values: &[f64],
+    ) -> Result<(), LJMError> {
         #[cfg(feature = "dynlink")]
-        let d_read_from_aadr: Symbol<extern "C" fn(i32, i32, i32, *mut c_double) -> i32> =
-            unsafe { LJMLibrary::get_c_function(b"LJM_eReadAddress")? };
+        let d_write_name_array: Symbol<
+            extern "C" fn(i32, *const c_char, i32, *const c_double, *mut i32) -> i32,
+        > = unsafe { LJMLibrary::get_c_function(b"LJM_eWriteNameArray")? };
 
-        let mut vtr = c_double::from(-1);
+        let ntw = CString::new(name_to_write).map_err(|_| LJMError::CStringConversionFailed)?;
 
+        let mut error_addr: i32 = -1;
         #[cfg(feature = "dynlink")]
-        let error_code = d_read_from_aadr(handle, addr, data_type, &mut vtr);
+        let error_code = d_write_name_array(
+            handle,
+            ntw.as_ptr(),
+            values.len() as i32,
+            values.as_ptr(),
+            &mut error_addr,
+        );
         #[cfg(feature = "staticlink")]
-        let error_code = unsafe { lib::LJM_eReadAddress(handle, addr, data_type, &mut vtr) };
+        let error_code = unsafe {
+            lib::LJM_eWriteNameArray(
+                handle,
+                ntw.as_ptr(),
+                values.len() as i32,
+                values.as_ptr(),
+                &mut error_addr,
+            )
+        };
 
-        LJMLibrary::error_code(vtr, error_code)
+        if error_code != 0 {
+            let frames = LJMLibrary::name_array_frames(&ntw, values.len());
+            return LJMLibrary::error_code_at((), error_code, error_addr, frames);
+        }
+
+        Ok(())
     }
 
-    /// Opens a LabJack and returns the handle id as an i32.
-    #[doc(alias = "LJM_OpenS")]
-    pub fn open_jack<T: Into<Vec<u8>>>(
-        device_type: DeviceType,
-        connection_type: ConnectionType,
-        identifier: T,
-    ) -> Result<i32, LJMError> {
+    #[doc(alias = "LJM_eWriteAddressArray")]
+    pub fn write_addr_array(
+        handle: i32,
+        address: i32,
+        data_type: i32,
+        values: &[f64],
+    ) -> Result<(), LJMError> {
         #[cfg(feature = "dynlink")]
-        let open_s: Symbol<
-            extern "C" fn(*const c_char, *const c_char, *const c_char, *mut i32) -> i32,
-        > = unsafe { LJMLibrary::get_c_function(b"LJM_OpenS")? };
-
-        let device_type =
-            CString::new(device_type.to_string()).map_err(|_| LJMError::CStringConversionFailed)?;
-        let connection_type = CString::new(connection_type.to_string())
-            .map_err(|_| LJMError::CStringConversionFailed)?;
-        let identifier = CString::new(identifier).map_err(|_| LJMError::CStringConversionFailed)?;
-
-        let mut handle_id: i32 = 0;
+        let d_write_addr_array: Symbol<
+            extern "C" fn(i32, i32, i32, i32, *const c_double, *mut i32) -> i32,
+        > = unsafe { LJMLibrary::get_c_function(b"LJM_eWriteAddressArray")? };
 
+        let mut error_addr: i32 = -1;
         #[cfg(feature = "dynlink")]
-        let error_code = open_s(
-            device_type.as_ptr(),
-            connection_type.as_ptr(),
-            identifier.as_ptr(),
-            &mut handle_id,
+        let error_code = d_write_addr_array(
+            handle,
+            address,
+            data_type,
+            values.len() as i32,
+            values.as_ptr(),
+            &mut error_addr,
         );
         #[cfg(feature = "staticlink")]
         let error_code = unsafe {
-            lib::LJM_OpenS(
-                device_type.as_ptr(),
-                connection_type.as_ptr(),
-                identifier.as_ptr(),
-                &mut handle_id,
+            lib::LJM_eWriteAddressArray(
+                handle,
+                address,
+                data_type,
+                values.len() as i32,
+                values.as_ptr(),
+                &mut error_addr,
             )
         };
 
-        LJMLibrary::error_code(handle_id, error_code)
+        if error_code != 0 {
+            let frames = LJMLibrary::array_frames(address, data_type, values.len());
+            return LJMLibrary::error_code_at((), error_code, error_addr, frames);
+        }
+
+        Ok(())
     }
 
-    /// Closes a LabJack given it's handle id as an i32.
-    #[doc(alias = "LJM_Close")]
-    pub fn close_jack(handle_id: i32) -> Result<i32, LJMError> {
+    /// Reads `size` consecutive values starting at the named register,
+    /// or `size` values from a buffer register.
+    #[doc(alias = "LJM_eReadNameArray")]
+    pub fn read_name_array<T: Into<Vec<u8>>>(
+        handle: i32,
+        name_to_read: T,
+        size: usize,
+    ) -> Result<Vec<f64>, LJMError> {
         #[cfg(feature = "dynlink")]
-        let close: Symbol<extern "C" fn(i32) -> i32> =
-            unsafe { LJMLibrary::get_c_function(b"LJM_Close")? };
+        let d_read_name_array: Symbol<
+            extern "C" fn(i32, *const c_char, i32, *mut c_double, *mut i32) -> i32,
+        > = unsafe { LJMLibrary::get_c_function(b"LJM_eReadNameArray")? };
 
-        #[cfg(feature = "dynlink")]
-        let error_code = close(handle_id);
+        let ntr = CString::new(name_to_read).map_err(|_| LJMError::CStringConversionFailed)?;
+        let mut values: Vec<c_double> = vec![0.0; size];
 
+        let mut error_addr: i32 = -1;
+        #[cfg(feature = "dynlink")]
+        let error_code = d_read_name_array(
+            handle,
+            ntr.as_ptr(),
+            size as i32,
+            values.as_mut_ptr(),
+            &mut error_addr,
+        );
         #[cfg(feature = "staticlink")]
-        let error_code = unsafe { lib::LJM_Close(handle_id) };
+        let error_code = unsafe {
+            lib::LJM_eReadNameArray(
+                handle,
+                ntr.as_ptr(),
+                size as i32,
+                values.as_mut_ptr(),
+                &mut error_addr,
+            )
+        };
 
-        LJMLibrary::error_code(handle_id, error_code)
+        if error_code != 0 {
+            let frames = LJMLibrary::name_array_frames(&ntr, size);
+            return LJMLibrary::error_code_at(values, error_code, error_addr, frames);
+        }
+
+        Ok(values)
     }
 
-    /// Closes all LabJacks connected.
-    #[doc(alias = "LJM_CloseAll")]
-    pub fn close_all() -> Result<(), LJMError> {
+    #[doc(alias = "LJM_eReadAddressArray")]
+    pub fn read_addr_array(
+        handle: i32,
+        address: i32,
+        data_type: i32,
+        size: usize,
+    ) -> Result<Vec<f64>, LJMError> {
         #[cfg(feature = "dynlink")]
-        let close_all: Symbol<extern "C" fn() -> i32> =
-            unsafe { LJMLibrary::get_c_function(b"LJM_CloseAll")? };
+        let d_read_addr_array: Symbol<
+            extern "C" fn(i32, i32, i32, i32, *mut c_double, *mut i32) -> i32,
+        > = unsafe { LJMLibrary::get_c_function(b"LJM_eReadAddressArray")? };
+
+        let mut values: Vec<c_double> = vec![0.0; size];
 
+        let mut error_addr: i32 = -1;
         #[cfg(feature = "dynlink")]
-        let error_code = close_all();
+        let error_code = d_read_addr_array(
+            handle,
+            address,
+            data_type,
+            size as i32,
+            values.as_mut_ptr(),
+            &mut error_addr,
+        );
+        #[cfg(feature = "staticlink")]
+        let error_code = unsafe {
+            lib::LJM_eReadAddressArray(
+                handle,
+                address,
+                data_type,
+                size as i32,
+                values.as_mut_ptr(),
+                &mut error_addr,
+            )
+        };
+
+        if error_code != 0 {
+            let frames = LJMLibrary::array_frames(address, data_type, size);
+            return LJMLibrary::error_code_at(values, error_code, error_addr, frames);
+        }
+
+        Ok(values)
+    }
+
+    // Validates a string to be written to a `LJM_STRING` register.
+    fn to_ljm_string(value: String) -> Result<CString, LJMError> {
+        if value.len() > LJM_STRING_MAX_SIZE {
+            return Err(LJMError::InvalidString(format!(
+                "String of {} bytes exceeds LJM_STRING_MAX_SIZE ({})",
+                value.len(),
+                LJM_STRING_MAX_SIZE
+            )));
+        }
+
+        CString::new(value).map_err(|_| LJMError::CStringConversionFailed)
+    }
+
+    // Recovers a string read from a `LJM_STRING` register.
+    fn from_ljm_string(buffer: &[c_char]) -> Result<String, LJMError> {
+        let as_vec = buffer
+            .iter()
+            .take_while(|v| **v != 0)
+            .map(|v| *v as u8)
+            .collect::<Vec<u8>>();
+
+        String::from_utf8(as_vec)
+            .map_err(|e| LJMError::InvalidString(format!("String is not valid UTF-8. {}", e)))
+    }
+
+    /// Writes a string of at most `LJM_STRING_MAX_SIZE` bytes
+    /// to a `LJM_STRING` register, such as `DEVICE_NAME_DEFAULT`.
+    #[doc(alias = "LJM_eWriteNameString")]
+    pub fn write_name_string<T: Into<Vec<u8>>, S: ToString>(
+        handle: i32,
+        name_to_write: T,
+        value_to_write: S,
+    ) -> Result<(), LJMError> {
+        let vtw = LJMLibrary::to_ljm_string(value_to_write.to_string())?;
 
+        #[cfg(feature = "dynlink")]
+        let d_write_name_string: Symbol<
+            extern "C" fn(i32, *const c_char, *const c_char) -> i32,
+        > = unsafe { LJMLibrary::get_c_function(b"LJM_eWriteNameString")? };
+
+        let ntw = CString::new(name_to_write).map_err(|_| LJMError::CStringConversionFailed)?;
+
+        #[cfg(feature = "dynlink")]
+        let error_code = d_write_name_string(handle, ntw.as_ptr(), vtw.as_ptr());
         #[cfg(feature = "staticlink")]
-        let error_code = unsafe { lib::LJM_CloseAll() };
+        let error_code = unsafe { lib::LJM_eWriteNameString(handle, ntw.as_ptr(), vtw.as_ptr()) };
 
         LJMLibrary::error_code((), error_code)
     }
 
-    /// Converts an IPV4 numerical representation, outputting the corresponding
-    /// decimal-dot notation for it.
-    ///
-    /// # Safety
-    /// This function is unsafe due to C pointer recovery. Different systems
-    /// will handle this behaviour differently, use with caution. Test experimentally,
-    /// before ever using in a production environment.
-    #[doc(alias = "LJM_NumberToIP")]
-    pub unsafe fn number_to_ip(number: i32) -> Result<String, LJMError> {
+    #[doc(alias = "LJM_eWriteAddressString")]
+    pub fn write_addr_string<S: ToString>(
+        handle: i32,
+        address: i32,
+        value_to_write: S,
+    ) -> Result<(), LJMError> {
+        let vtw = LJMLibrary::to_ljm_string(value_to_write.to_string())?;
+
         #[cfg(feature = "dynlink")]
-        let d_number_to_ip: Symbol<
-            extern "C" fn(::std::os::raw::c_uint, *mut c_char) -> i32,
-        > = unsafe { LJMLibrary::get_c_function(b"LJM_NumberToIP")? };
+        let d_write_addr_string: Symbol<extern "C" fn(i32, i32, *const c_char) -> i32> =
+            unsafe { LJMLibrary::get_c_function(b"LJM_eWriteAddressString")? };
 
-        let number: c_uint = c_uint::try_from(number).map_err(|error| {
-            LJMError::LibraryError(format!(
-                "Unable to convert number into C unsigned integer. {}",
-                error
-            ))
-        })?;
+        #[cfg(feature = "dynlink")]
+        let error_code = d_write_addr_string(handle, address, vtw.as_ptr());
+        #[cfg(feature = "staticlink")]
+        let error_code = unsafe { lib::LJM_eWriteAddressString(handle, address, vtw.as_ptr()) };
 
-        let ip_address =
-            CString::new("000.000.000.000").map_err(|_| LJMError::CStringConversionFailed)?;
-        let ip_pointer = ip_address.into_raw();
+        LJMLibrary::error_code((), error_code)
+    }
 
+    /// Reads a string from a `LJM_STRING` register, such as `DEVICE_NAME_DEFAULT`.
+    #[doc(alias = "LJM_eReadNameString")]
+    pub fn read_name_string<T: Into<Vec<u8>>>(
+        handle: i32,
+        name_to_read: T,
+    ) -> Result<String, LJMError> {
         #[cfg(feature = "dynlink")]
-        let error_code = d_number_to_ip(number, ip_pointer);
+        let d_read_name_string: Symbol<
+            extern "C" fn(i32, *const c_char, *mut c_char) -> i32,
+        > = unsafe { LJMLibrary::get_c_function(b"LJM_eReadNameString")? };
+
+        let ntr = CString::new(name_to_read).map_err(|_| LJMError::CStringConversionFailed)?;
+        let mut buffer: [c_char; LJM_STRING_ALLOCATION_SIZE] = [0; LJM_STRING_ALLOCATION_SIZE];
+
+        #[cfg(feature = "dynlink")]
+        let error_code = d_read_name_string(handle, ntr.as_ptr(), buffer.as_mut_ptr());
         #[cfg(feature = "staticlink")]
-        let error_code = unsafe { lib::LJM_NumberToIP(number, ip_pointer) };
+        let error_code =
+            unsafe { lib::LJM_eReadNameString(handle, ntr.as_ptr(), buffer.as_mut_ptr()) };
 
-        let retrieved_pointer = unsafe { CString::from_raw(ip_pointer) };
-        let recovered_ip = retrieved_pointer.into_string().map_err(|error| {
-            LJMError::LibraryError(format!("Unable to retrieve IP pointer. {}", error))
-        })?;
+        LJMLibrary::error_code((), error_code)?;
+        LJMLibrary::from_ljm_string(&buffer)
+    }
 
-        LJMLibrary::error_code(recovered_ip, error_code)
+    #[doc(alias = "LJM_eReadAddressString")]
+    pub fn read_addr_string(handle: i32, address: i32) -> Result<String, LJMError> {
+        #[cfg(feature = "dynlink")]
+        let d_read_addr_string: Symbol<extern "C" fn(i32, i32, *mut c_char) -> i32> =
+            unsafe { LJMLibrary::get_c_function(b"LJM_eReadAddressString")? };
+
+        let mut buffer: [c_char; LJM_STRING_ALLOCATION_SIZE] = [0; LJM_STRING_ALLOCATION_SIZE];
+
+        #[cfg(feature = "dynlink")]
+        let error_code = d_read_addr_string(handle, address, buffer.as_mut_ptr());
+        #[cfg(feature = "staticlink")]
+        let error_code =
+            unsafe { lib::LJM_eReadAddressString(handle, address, buffer.as_mut_ptr()) };
+
+        LJMLibrary::error_code((), error_code)?;
+        LJMLibrary::from_ljm_string(&buffer)
     }
 
-    /// Informs regarding device connection type
-    #[doc(alias = "LJM_GetHandleInfo")]
-    pub fn get_handle_info(handle: i32) -> Result<DeviceHandleInfo, LJMError> {
+    #[doc(alias = "LJM_eWriteNameByteArray")]
+    pub fn write_name_byte_array<T: Into<Vec<u8>>, B: Into<Vec<u8>>>(
+        handle: i32,
+        name_to_write: T,
+        size: i32,
+        bytes: B,
+    ) -> Result<(), LJMError> {
         #[cfg(feature = "dynlink")]
-        let get_handle_info: Symbol<
-            extern "C" fn(i32, *mut i32, *mut i32, *mut i32, *mut i32, *mut i32, *mut i32) -> i32,
-        > = unsafe { LJMLibrary::get_c_function(b"LJM_GetHandleInfo")? };
+        let d_write_name_byte_array: Symbol<
+            extern "C" fn(i32, *const c_char, i32, *const c_char, *mut i32) -> i32,
+        > = unsafe { LJMLibrary::get_c_function(b"LJM_eWriteNameByteArray")? };
 
-        let mut device_type: i32 = 0;
-        let mut connection_type: i32 = 0;
-        let mut serial_number: i32 = 0;
-        let mut ip_address: i32 = 0;
-        let mut port: i32 = 0;
-        let mut max_bytes_per_megabyte: i32 = 0;
+        let btw = CString::new(bytes).map_err(|_| LJMError::CStringConversionFailed)?; // Bytes-To-Write
+        let ntw = CString::new(name_to_write).map_err(|_| LJMError::CStringConversionFailed)?; // Name-To-Write
 
+        let mut error_addr: i32 = 0;
         #[cfg(feature = "dynlink")]
-        let error_code = get_handle_info(
+        let error_code =
+            d_write_name_byte_array(handle, ntw.as_ptr(), size, btw.as_ptr(), &mut error_addr);
+        #[cfg(feature = "staticlink")]
+        let error_code = unsafe {
+            lib::LJM_eWriteNameByteArray(handle, ntw.as_ptr(), size, btw.as_ptr(), &mut error_addr)
+        };
+
+        LJMLibrary::error_code((), error_code)
+    }
+
+    #[doc(alias = "LJM_eReadNameByteArray")]
+    pub fn read_name_byte_array<T: Into<Vec<u8>>>(
+        handle: i32,
+        name_to_read: T,
+        size: i32,
+    ) -> Result<Vec<u8>, LJMError> {
+        #[cfg(feature = "dynlink")]
+        let d_write_name_byte_array: Symbol<
+            extern "C" fn(i32, *const c_char, i32, *mut u8, *mut i32) -> i32,
+        > = unsafe { LJMLibrary::get_c_function(b"LJM_eReadNameByteArray")? };
+
+        // Only allocate as much as we need, and do so such that
+        // it will be initialized when written to - the C lib
+        // does not know the context, so we'll Box it (heap-alloc)
+        // with a static size of `size`.
+        #[cfg(feature = "staticlink")]
+        let mut buffer: Box<[c_char]> = vec![c_char::from(0); size as usize].into_boxed_slice();
+        #[cfg(feature = "dynlink")]
+        let mut buffer: Box<[u8]> = vec![0u8; size as usize].into_boxed_slice();
+
+        let ntr = CString::new(name_to_read).map_err(|_| LJMError::CStringConversionFailed)?; // Name-To-Write
+
+        let mut error_addr: i32 = 0;
+        #[cfg(feature = "dynlink")]
+        let error_code = d_write_name_byte_array(
             handle,
-            &mut device_type,
-            &mut connection_type,
-            &mut serial_number,
-            &mut ip_address,
-            &mut port,
-            &mut max_bytes_per_megabyte,
+            ntr.as_ptr(),
+            size,
+            buffer.as_mut_ptr(),
+            &mut error_addr,
         );
         #[cfg(feature = "staticlink")]
         let error_code = unsafe {
-            lib::LJM_GetHandleInfo(
+            lib::LJM_eWriteNameByteArray(
                 handle,
-                &mut device_type,
-                &mut connection_type,
-                &mut serial_number,
-                &mut ip_address,
-                &mut port,
-                &mut max_bytes_per_megabyte,
+                ntr.as_ptr(),
+                size,
+                buffer.as_mut_ptr(),
+                &mut error_addr,
             )
         };
 
-        LJMLibrary::error_code(
-            DeviceHandleInfo {
-                device_type: DeviceType::from(device_type),
-                connection_type: ConnectionType::from(connection_type),
-                ip_address,
-                serial_number,
-                port,
-                max_bytes_per_megabyte,
-            },
-            error_code,
-        )
+        #[cfg(feature = "staticlink")]
+        let as_vec = buffer.to_vec().into_iter().map(|v| v as u8).collect();
+
+        #[cfg(feature = "dynlink")]
+        let as_vec = buffer.into_vec();
+
+        LJMLibrary::error_code(as_vec, error_code)
     }
 
-    /// This returns a boolean for the stream state (on/off)
-    /// for a specific device handle. There may still be streams
-    /// activated for other devices, however the function will
-    /// only give the stream state for the specified handle.
-    #[cfg(feature = "stream")]
-    pub fn is_stream_active(handle_id: i32) -> bool {
-        let wrapper = LJM_WRAPPER.get();
+    /// Reads from a labjack given the handle and name to read.
+    /// Returns an f64 value that is read from the labjack.
+    #[doc(alias = "LJM_eReadName")]
+    pub fn read_name<T: Into<Vec<u8>>>(handle: i32, name_to_read: T) -> Result<f64, LJMError> {
+        #[cfg(feature = "dynlink")]
+        let d_read_from_aadr: Symbol<
+            extern "C" fn(i32, *const c_char, *mut c_double) -> i32,
+        > = unsafe { LJMLibrary::get_c_function(b"LJM_eReadName")? };
 
-        match wrapper {
-            Some(w) => {
-                let stream = w.stream.read();
+        let ntr = CString::new(name_to_read).map_err(|_| LJMError::CStringConversionFailed)?;
+        let mut vtr = c_double::from(-1);
 
-                match stream {
-                    Ok(s) => s.get(&handle_id).is_some(),
-                    Err(_) => false,
-                }
-            }
-            None => false,
-        }
+        #[cfg(feature = "dynlink")]
+        let error_code = d_read_from_aadr(handle, ntr.as_ptr(), &mut vtr);
+        #[cfg(feature = "staticlink")]
+        let error_code = unsafe { lib::LJM_eReadName(handle, ntr.as_ptr(), &mut vtr) };
+
+        LJMLibrary::error_code(vtr, error_code)
     }
 
-    /// Starts a LJM Stream, stopped with `stream_stop`.
-    /// Returns actual device scan rate (chosen by LabJack).
-    ///
-    /// As opposed to `stream_start`, accepts any Vec<T> where T: ToString.
-    /// Such that, you may provide the string-representation of LJM addresses,
-    /// permitting each can be decoded by LJMLibrary::name_to_address into a
-    /// logical LJM address.
+    #[doc(alias = "LJM_eReadName")]
+    pub fn read_addr(handle: i32, addr: i32, data_type: i32) -> Result<f64, LJMError> {
+        #[cfg(feature = "dynlink")]
+        let d_read_from_aadr: Symbol<extern "C" fn(i32, i32, i32, *mut c_double) -> i32> =
+            unsafe { LJMLibrary::get_c_function(b"LJM_eReadAddress")? };
+
+        let mut vtr = c_double::from(-1);
+
+        #[cfg(feature = "dynlink")]
+        let error_code = d_read_from_aadr(handle, addr, data_type, &mut vtr);
+        #[cfg(feature = "staticlink")]
+        let error_code = unsafe { lib::LJM_eReadAddress(handle, addr, data_type, &mut vtr) };
+
+        LJMLibrary::error_code(vtr, error_code)
+    }
+
+    /// Reads multiple registers by name in as few packets as possible.
+    /// Returns the values in the same order as `names_to_read`.
     ///
-    /// `suggested_scan_rate` The scan rate forwarded to LJM which it will attempt to use
+    /// If a register fails, the `ErrorFrame` of the `LJMError::ErrorCode` identifies
+    /// the index of the failing name.
+    #[doc(alias = "LJM_eReadNames")]
+    pub fn read_names<T: ToString>(handle: i32, names_to_read: &[T]) -> Result<Vec<f64>, LJMError> {
+        #[cfg(feature = "dynlink")]
+        let d_read_names: Symbol<
+            extern "C" fn(i32, i32, *const *const c_char, *mut c_double, *mut i32) -> i32,
+        > = unsafe { LJMLibrary::get_c_function(b"LJM_eReadNames")? };
+
+        let names = names_to_read
+            .iter()
+            .map(|name| CString::new(name.to_string()))
+            .collect::<Result<Vec<CString>, _>>()
+            .map_err(|_| LJMError::CStringConversionFailed)?;
+        let mut name_ptrs: Vec<*const c_char> = names.iter().map(|name| name.as_ptr()).collect();
+
+        let mut values: Vec<c_double> = vec![0.0; names.len()];
+        let mut error_addr: i32 = -1;
+
+        #[cfg(feature = "dynlink")]
+        let error_code = d_read_names(
+            handle,
+            names.len() as i32,
+            name_ptrs.as_mut_ptr(),
+            values.as_mut_ptr(),
+            &mut error_addr,
+        );
+        #[cfg(feature = "staticlink")]
+        let error_code = unsafe {
+            lib::LJM_eReadNames(
+                handle,
+                names.len() as i32,
+                name_ptrs.as_mut_ptr(),
+                values.as_mut_ptr(),
+                &mut error_addr,
+            )
+        };
+
+        if error_code != 0 {
+            let frames = LJMLibrary::frame_addresses(&names);
+            return LJMLibrary::error_code_at(values, error_code, error_addr, frames);
+        }
+
+        Ok(values)
+    }
+
+    /// Reads multiple registers given as `(address, data_type)` pairs
+    /// in as few packets as possible. Returns the values in the same
+    /// order as `addresses`.
+    ///
+    /// If a register fails, the `ErrorFrame` of the `LJMError::ErrorCode` identifies
+    /// the index of the failing address.
+    #[doc(alias = "LJM_eReadAddresses")]
+    pub fn read_addrs(handle: i32, addresses: &[(i32, i32)]) -> Result<Vec<f64>, LJMError> {
+        #[cfg(feature = "dynlink")]
+        let d_read_addrs: Symbol<
+            extern "C" fn(i32, i32, *const i32, *const i32, *mut c_double, *mut i32) -> i32,
+        > = unsafe { LJMLibrary::get_c_function(b"LJM_eReadAddresses")? };
+
+        let (addrs, types): (Vec<i32>, Vec<i32>) = addresses.iter().copied().unzip();
+
+        let mut values: Vec<c_double> = vec![0.0; addrs.len()];
+        let mut error_addr: i32 = -1;
+
+        #[cfg(feature = "dynlink")]
+        let error_code = d_read_addrs(
+            handle,
+            addrs.len() as i32,
+            addrs.as_ptr(),
+            types.as_ptr(),
+            values.as_mut_ptr(),
+            &mut error_addr,
+        );
+        #[cfg(feature = "staticlink")]
+        let error_code = unsafe {
+            lib::LJM_eReadAddresses(
+                handle,
+                addrs.len() as i32,
+                addrs.as_ptr(),
+                types.as_ptr(),
+                values.as_mut_ptr(),
+                &mut error_addr,
+            )
+        };
+
+        LJMLibrary::error_code_at(
+            values,
+            error_code,
+            error_addr,
+            addrs.iter().copied().map(Some),
+        )
+    }
+
+    /// Opens a LabJack and returns the handle id as an i32.
+    #[doc(alias = "LJM_OpenS")]
+    pub fn open_jack<T: Into<Vec<u8>>>(
+        device_type: DeviceType,
+        connection_type: ConnectionType,
+        identifier: T,
+    ) -> Result<i32, LJMError> {
+        #[cfg(feature = "dynlink")]
+        let open_s: Symbol<
+            extern "C" fn(*const c_char, *const c_char, *const c_char, *mut i32) -> i32,
+        > = unsafe { LJMLibrary::get_c_function(b"LJM_OpenS")? };
+
+        let device_type =
+            CString::new(device_type.to_string()).map_err(|_| LJMError::CStringConversionFailed)?;
+        let connection_type = CString::new(connection_type.to_string())
+            .map_err(|_| LJMError::CStringConversionFailed)?;
+        let identifier = CString::new(identifier).map_err(|_| LJMError::CStringConversionFailed)?;
+
+        let mut handle_id: i32 = 0;
+
+        #[cfg(feature = "dynlink")]
+        let error_code = open_s(
+            device_type.as_ptr(),
+            connection_type.as_ptr(),
+            identifier.as_ptr(),
+            &mut handle_id,
+        );
+        #[cfg(feature = "staticlink")]
+        let error_code = unsafe {
+            lib::LJM_OpenS(
+                device_type.as_ptr(),
+                connection_type.as_ptr(),
+                identifier.as_ptr(),
+                &mut handle_id,
+            )
+        };
+
+        LJMLibrary::error_code(handle_id, error_code)
+    }
+
+    /// Scans for LabJack devices which could be opened, filtered
+    /// by the given device and connection type (`ANY` is permitted).
+    #[doc(alias = "LJM_ListAllS")]
+    #[doc(alias = "LJM_ListAll")]
+    pub fn list_all(
+        device_type: DeviceType,
+        connection_type: ConnectionType,
+    ) -> Result<Vec<DiscoveredDevice>, LJMError> {
+        #[cfg(feature = "dynlink")]
+        let list_all_s: Symbol<
+            extern "C" fn(
+                *const c_char,
+                *const c_char,
+                *mut i32,
+                *mut i32,
+                *mut i32,
+                *mut i32,
+                *mut i32,
+            ) -> i32,
+        > = unsafe { LJMLibrary::get_c_function(b"LJM_ListAllS")? };
+
+        let device_type =
+            CString::new(device_type.to_string()).map_err(|_| LJMError::CStringConversionFailed)?;
+        let connection_type = CString::new(connection_type.to_string())
+            .map_err(|_| LJMError::CStringConversionFailed)?;
+
+        let mut num_found: i32 = 0;
+        let mut device_types = [0i32; LJM_LIST_ALL_SIZE];
+        let mut connection_types = [0i32; LJM_LIST_ALL_SIZE];
+        let mut serial_numbers = [0i32; LJM_LIST_ALL_SIZE];
+        let mut ip_addresses = [0i32; LJM_LIST_ALL_SIZE];
+
+        #[cfg(feature = "dynlink")]
+        let error_code = list_all_s(
+            device_type.as_ptr(),
+            connection_type.as_ptr(),
+            &mut num_found,
+            device_types.as_mut_ptr(),
+            connection_types.as_mut_ptr(),
+            serial_numbers.as_mut_ptr(),
+            ip_addresses.as_mut_ptr(),
+        );
+        #[cfg(feature = "staticlink")]
+        let error_code = unsafe {
+            lib::LJM_ListAllS(
+                device_type.as_ptr(),
+                connection_type.as_ptr(),
+                &mut num_found,
+                device_types.as_mut_ptr(),
+                connection_types.as_mut_ptr(),
+                serial_numbers.as_mut_ptr(),
+                ip_addresses.as_mut_ptr(),
+            )
+        };
+
+        let devices = (0..(num_found.max(0) as usize).min(LJM_LIST_ALL_SIZE))
+            .map(|i| DiscoveredDevice {
+                device_type: DeviceType::from(device_types[i]),
+                connection_type: ConnectionType::from(connection_types[i]),
+                serial_number: serial_numbers[i],
+                ip_address: Ipv4Addr::from(ip_addresses[i] as u32),
+            })
+            .collect();
+
+        LJMLibrary::error_code(devices, error_code)
+    }
+
+    /// Scans for LabJack devices as in `list_all`, additionally reading
+    /// the given registers (such as `DEVICE_NAME_DEFAULT` or `FIRMWARE_VERSION`)
+    /// from each device found.
+    ///
+    /// Registers must have a fixed size, so `LJM_BYTE` typed registers are not
+    /// supported and will return a `LJMError::LibraryError`.
+    #[doc(alias = "LJM_ListAllExtended")]
+    #[allow(clippy::type_complexity)]
+    pub fn list_all_extended<T>(
+        device_type: DeviceType,
+        connection_type: ConnectionType,
+        registers: Vec<T>,
+    ) -> Result<Vec<ExtendedDiscoveredDevice>, LJMError>
+    where
+        T: ToString,
+    {
+        #[cfg(feature = "dynlink")]
+        let list_all_extended: Symbol<
+            extern "C" fn(
+                i32,
+                i32,
+                i32,
+                *const i32,
+                *const i32,
+                i32,
+                *mut i32,
+                *mut i32,
+                *mut i32,
+                *mut i32,
+                *mut i32,
+                *mut u8,
+            ) -> i32,
+        > = unsafe { LJMLibrary::get_c_function(b"LJM_ListAllExtended")? };
+
+        let names: Vec<String> = registers.iter().map(|r| r.to_string()).collect();
+
+        let mut addresses: Vec<i32> = Vec::with_capacity(names.len());
+        let mut data_types: Vec<i32> = Vec::with_capacity(names.len());
+        let mut num_regs: Vec<i32> = Vec::with_capacity(names.len());
+
+        for name in &names {
+            let (address, data_type) = LJMLibrary::name_to_address(name)?;
+            let regs = DiscoveredRegister::registers_for_type(data_type).ok_or_else(|| {
+                LJMError::LibraryError(format!("Register {} does not have a fixed size", name))
+            })?;
+
+            addresses.push(address);
+            data_types.push(data_type);
+            num_regs.push(regs);
+        }
+
+        // Each device has its queried bytes laid out sequentially.
+        let bytes_per_device = num_regs.iter().sum::<i32>() as usize * 2;
+
+        let mut num_found: i32 = 0;
+        let mut device_types = [0i32; LJM_LIST_ALL_SIZE];
+        let mut connection_types = [0i32; LJM_LIST_ALL_SIZE];
+        let mut serial_numbers = [0i32; LJM_LIST_ALL_SIZE];
+        let mut ip_addresses = [0i32; LJM_LIST_ALL_SIZE];
+        let mut bytes = vec![0u8; LJM_LIST_ALL_SIZE * bytes_per_device];
+
+        #[cfg(feature = "dynlink")]
+        let error_code = list_all_extended(
+            i32::from(&device_type),
+            i32::from(&connection_type),
+            addresses.len() as i32,
+            addresses.as_ptr(),
+            num_regs.as_ptr(),
+            LJM_LIST_ALL_SIZE as i32,
+            &mut num_found,
+            device_types.as_mut_ptr(),
+            connection_types.as_mut_ptr(),
+            serial_numbers.as_mut_ptr(),
+            ip_addresses.as_mut_ptr(),
+            bytes.as_mut_ptr(),
+        );
+        #[cfg(feature = "staticlink")]
+        let error_code = unsafe {
+            lib::LJM_ListAllExtended(
+                i32::from(&device_type),
+                i32::from(&connection_type),
+                addresses.len() as i32,
+                addresses.as_ptr(),
+                num_regs.as_ptr(),
+                LJM_LIST_ALL_SIZE as i32,
+                &mut num_found,
+                device_types.as_mut_ptr(),
+                connection_types.as_mut_ptr(),
+                serial_numbers.as_mut_ptr(),
+                ip_addresses.as_mut_ptr(),
+                bytes.as_mut_ptr(),
+            )
+        };
+
+        let devices = (0..(num_found.max(0) as usize).min(LJM_LIST_ALL_SIZE))
+            .map(|i| {
+                let mut offset = i * bytes_per_device;
+
+                let registers = names
+                    .iter()
+                    .enumerate()
+                    .map(|(j, name)| {
+                        let length = num_regs[j] as usize * 2;
+                        let register = DiscoveredRegister {
+                            name: name.clone(),
+                            address: addresses[j],
+                            data_type: data_types[j],
+                            bytes: bytes[offset..offset + length].to_vec(),
+                        };
+
+                        offset += length;
+                        register
+                    })
+                    .collect();
+
+                ExtendedDiscoveredDevice {
+                    device: DiscoveredDevice {
+                        device_type: DeviceType::from(device_types[i]),
+                        connection_type: ConnectionType::from(connection_types[i]),
+                        serial_number: serial_numbers[i],
+                        ip_address: Ipv4Addr::from(ip_addresses[i] as u32),
+                    },
+                    registers,
+                }
+            })
+            .collect();
+
+        LJMLibrary::error_code(devices, error_code)
+    }
+
+    /// Closes a LabJack given it's handle id as an i32.
+    #[doc(alias = "LJM_Close")]
+    pub fn close_jack(handle_id: i32) -> Result<i32, LJMError> {
+        #[cfg(feature = "dynlink")]
+        let close: Symbol<extern "C" fn(i32) -> i32> =
+            unsafe { LJMLibrary::get_c_function(b"LJM_Close")? };
+
+        #[cfg(feature = "dynlink")]
+        let error_code = close(handle_id);
+
+        #[cfg(feature = "staticlink")]
+        let error_code = unsafe { lib::LJM_Close(handle_id) };
+
+        LJMLibrary::error_code(handle_id, error_code)
+    }
+
+    /// Closes all LabJacks connected.
+    #[doc(alias = "LJM_CloseAll")]
+    pub fn close_all() -> Result<(), LJMError> {
+        #[cfg(feature = "dynlink")]
+        let close_all: Symbol<extern "C" fn() -> i32> =
+            unsafe { LJMLibrary::get_c_function(b"LJM_CloseAll")? };
+
+        #[cfg(feature = "dynlink")]
+        let error_code = close_all();
+
+        #[cfg(feature = "staticlink")]
+        let error_code = unsafe { lib::LJM_CloseAll() };
+
+        LJMLibrary::error_code((), error_code)
+    }
+
+    /// Converts an IPV4 numerical representation, outputting the corresponding
+    /// decimal-dot notation for it.
+    ///
+    /// # Safety
+    /// This function is unsafe due to C pointer recovery. Different systems
+    /// will handle this behaviour differently, use with caution. Test experimentally,
+    /// before ever using in a production environment.
+    #[doc(alias = "LJM_NumberToIP")]
+    pub unsafe fn number_to_ip(number: i32) -> Result<String, LJMError> {
+        #[cfg(feature = "dynlink")]
+        let d_number_to_ip: Symbol<
+            extern "C" fn(::std::os::raw::c_uint, *mut c_char) -> i32,
+        > = unsafe { LJMLibrary::get_c_function(b"LJM_NumberToIP")? };
+
+        let number: c_uint = c_uint::try_from(number).map_err(|error| {
+            LJMError::LibraryError(format!(
+                "Unable to convert number into C unsigned integer. {}",
+                error
+            ))
+        })?;
+
+        let ip_address =
+            CString::new("000.000.000.000").map_err(|_| LJMError::CStringConversionFailed)?;
+        let ip_pointer = ip_address.into_raw();
+
+        #[cfg(feature = "dynlink")]
+        let error_code = d_number_to_ip(number, ip_pointer);
+        #[cfg(feature = "staticlink")]
+        let error_code = unsafe { lib::LJM_NumberToIP(number, ip_pointer) };
+
+        let retrieved_pointer = unsafe { CString::from_raw(ip_pointer) };
+        let recovered_ip = retrieved_pointer.into_string().map_err(|error| {
+            LJMError::LibraryError(format!("Unable to retrieve IP pointer. {}", error))
+        })?;
+
+        LJMLibrary::error_code(recovered_ip, error_code)
+    }
+
+    /// Informs regarding device connection type
+    #[doc(alias = "LJM_GetHandleInfo")]
+    pub fn get_handle_info(handle: i32) -> Result<DeviceHandleInfo, LJMError> {
+        #[cfg(feature = "dynlink")]
+        let get_handle_info: Symbol<
+            extern "C" fn(i32, *mut i32, *mut i32, *mut i32, *mut i32, *mut i32, *mut i32) -> i32,
+        > = unsafe { LJMLibrary::get_c_function(b"LJM_GetHandleInfo")? };
+
+        let mut device_type: i32 = 0;
+        let mut connection_type: i32 = 0;
+        let mut serial_number: i32 = 0;
+        let mut ip_address: i32 = 0;
+        let mut port: i32 = 0;
+        let mut max_bytes_per_megabyte: i32 = 0;
+
+        #[cfg(feature = "dynlink")]
+        let error_code = get_handle_info(
+            handle,
+            &mut device_type,
+            &mut connection_type,
+            &mut serial_number,
+            &mut ip_address,
+            &mut port,
+            &mut max_bytes_per_megabyte,
+        );
+        #[cfg(feature = "staticlink")]
+        let error_code = unsafe {
+            lib::LJM_GetHandleInfo(
+                handle,
+                &mut device_type,
+                &mut connection_type,
+                &mut serial_number,
+                &mut ip_address,
+                &mut port,
+                &mut max_bytes_per_megabyte,
+            )
+        };
+
+        LJMLibrary::error_code(
+            DeviceHandleInfo {
+                device_type: DeviceType::from(device_type),
+                connection_type: ConnectionType::from(connection_type),
+                ip_address,
+                serial_number,
+                port,
+                max_bytes_per_megabyte,
+            },
+            error_code,
+        )
+    }
+
+    /// This returns a boolean for the stream state (on/off)
+    /// for a specific device handle. There may still be streams
+    /// activated for other devices, however the function will
+    /// only give the stream state for the specified handle.
+    #[cfg(feature = "stream")]
+    pub fn is_stream_active(handle_id: i32) -> bool {
+        let wrapper = LJM_WRAPPER.get();
+
+        match wrapper {
+            Some(w) => {
+                let stream = w.stream.read();
+
+                match stream {
+                    Ok(s) => s.get(&handle_id).is_some(),
+                    Err(_) => false,
+                }
+            }
+            None => false,
+        }
+    }
+
+    /// Starts a LJM Stream, stopped with `stream_stop`.
+    /// Returns actual device scan rate (chosen by LabJack).
+    ///
+    /// As opposed to `stream_start`, accepts any Vec<T> where T: ToString.
+    /// Such that, you may provide the string-representation of LJM addresses,
+    /// permitting each can be decoded by LJMLibrary::name_to_address into a
+    /// logical LJM address.
+    ///
+    /// `suggested_scan_rate` The scan rate forwarded to LJM which it will attempt to use
+    ///
+    /// 32-bit integer registers (such as `DIO0_EF_READ_A` or `CORE_TIMER`) only
+    /// stream their lower 16 bits, so `STREAM_DATA_CAPTURE_16` is streamed after
+    /// each of them, and reads return the combined 32-bit value in place of the pair.
+    /// Registers already followed by `STREAM_DATA_CAPTURE_16` are left as given.
+    ///
+    #[doc(alias = "LJM_eStreamStart")]
+    #[cfg(feature = "stream")]
+    pub fn stream_start_addr<T>(
+        handle: i32,
+        scans_per_read: i32,
+        suggested_scan_rate: f64,
+        streams: Vec<T>,
+    ) -> Result<f64, LJMError>
+    where
+        T: ToString + Display,
+    {
+        let registers: Result<Vec<(i32, i32)>, LJMError> =
+            streams.iter().try_fold(Vec::new(), |mut acc, a| {
+                acc.push(LJMLibrary::name_to_address(a)?);
+                Ok(acc)
+            });
+        let registers = registers?;
+
+        let captures = registers
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| {
+                registers.get(index + 1).map(|(address, _)| *address)
+                    != Some(STREAM_DATA_CAPTURE_16_ADDRESS)
+            })
+            .filter_map(|(index, (_, data_type))| Capture::for_type(index, *data_type))
+            .collect();
+
+        let addresses = registers.into_iter().map(|(address, _)| address).collect();
+
+        // Retained so stream frames may be indexed by the original register names.
+        let names = streams.iter().map(|name| name.to_string()).collect();
+
+        LJMLibrary::start_stream(
+            handle,
+            scans_per_read,
+            suggested_scan_rate,
+            addresses,
+            names,
+            captures,
+        )
+    }
+
+    /// Starts a LJM Stream, stopped with `stream_stop`.
+    /// Returns actual device scan rate (chosen by LabJack)
+    ///
+    /// `suggested_scan_rate` The scan rate forwarded to LJM which it will attempt to use
+    ///
+    /// Any stream-outs configured for the handle (such as by `periodic_stream_out`)
+    /// are appended to the scan list, if not already present.
+    ///
+    #[doc(alias = "LJM_eStreamStart")]
+    #[cfg(feature = "stream")]
+    pub fn stream_start(
+        handle: i32,
+        scans_per_read: i32,
+        suggested_scan_rate: f64,
+        addresses: Vec<i32>,
+    ) -> Result<f64, LJMError> {
+        let names = addresses
+            .iter()
+            .map(|address| address.to_string())
+            .collect();
+
+        LJMLibrary::start_stream(
+            handle,
+            scans_per_read,
+            suggested_scan_rate,
+            addresses,
+            names,
+            Vec::new(),
+        )
+    }
+
+    // Starts the stream, recording `names` (one per address) as the channel names,
+    // with the upper 16 bits of each of the `captures` streamed after it.
+    #[cfg(feature = "stream")]
+    fn start_stream(
+        handle: i32,
+        scans_per_read: i32,
+        suggested_scan_rate: f64,
+        mut addresses: Vec<i32>,
+        mut names: Vec<String>,
+        captures: Vec<Capture>,
+    ) -> Result<f64, LJMError> {
+        // Recordings hold the combined values, so need no captures.
+        if let Some(scan_rate) = LJMLibrary::start_replay(handle, &addresses)? {
+            let layout = CaptureLayout::new(addresses.len(), Vec::new());
+            LJMLibrary::register_stream(
+                handle,
+                scans_per_read,
+                scan_rate,
+                addresses,
+                names,
+                layout,
+            )?;
+            return Ok(scan_rate);
+        }
+
+        #[cfg(feature = "dynlink")]
+        let stream_start: Symbol<
+            extern "C" fn(i32, i32, i32, *const i32, *mut c_double) -> i32,
+        > = unsafe { LJMLibrary::get_c_function(b"LJM_eStreamStart")? };
+
+        let stream_outs = LJM_WRAPPER
+            .get()
+            .ok_or(LJMError::Uninitialized)?
+            .stream_out
+            .read()
+            .map_err(|_| LJMError::PoisonedLock)?
+            .get(&handle)
+            .cloned()
+            .unwrap_or_default();
+
+        for stream_out in stream_outs {
+            if !addresses.contains(&stream_out) {
+                addresses.push(stream_out);
+                names.push(LJMLibrary::stream_out_name(stream_out).unwrap_or_default());
+            }
+        }
+
+        let layout = CaptureLayout::new(addresses.len(), captures);
+        let scan_list = layout.scan_list(&addresses);
+
+        let addr_slice: &[i32] = &scan_list;
+        let mut scan_rate: f64 = suggested_scan_rate;
+
+        #[cfg(feature = "dynlink")]
+        let error_code = stream_start(
+            handle,
+            scans_per_read,
+            scan_list.len() as i32,
+            addr_slice.as_ptr(),
+            &mut scan_rate,
+        );
+        #[cfg(feature = "staticlink")]
+        let error_code = unsafe {
+            lib::LJM_eStreamStart(
+                handle,
+                scans_per_read,
+                scan_list.len() as i32,
+                addr_slice.as_ptr(),
+                &mut scan_rate,
+            )
+        };
+
+        // If we don't have an error we will initialize the stream
+        if error_code == 0 {
+            // The stream-outs are now part of this stream's scan list.
+            LJM_WRAPPER
+                .get()
+                .ok_or(LJMError::Uninitialized)?
+                .stream_out
+                .write()
+                .map_err(|_| LJMError::PoisonedLock)?
+                .remove(&handle);
+
+            LJMLibrary::register_stream(
+                handle,
+                scans_per_read,
+                scan_rate,
+                addresses,
+                names,
+                layout,
+            )?;
+        }
+
+        LJMLibrary::error_code(scan_rate, error_code)
+    }
+
+    #[cfg(feature = "stream")]
+    fn register_stream(
+        handle: i32,
+        scans_per_read: i32,
+        scan_rate: f64,
+        addresses: Vec<i32>,
+        names: Vec<String>,
+        layout: CaptureLayout,
+    ) -> Result<(), LJMError> {
+        let mut stream = LJM_WRAPPER
+            .get()
+            .ok_or(LJMError::Uninitialized)?
+            .stream
+            .write()
+            .map_err(|_| LJMError::PoisonedLock)?;
+
+        stream.insert(
+            handle,
+            LJMStream {
+                clock: Arc::new(
+                    StreamClock::new(scan_rate, &addresses).with_captures(layout.captures()),
+                ),
+                scan_list: addresses,
+                capture_buffer: Arc::new(Mutex::new(Vec::new())),
+                layout,
+                names,
+                scans_per_read,
+                callback: None,
+                retired_callbacks: Vec::new(),
+                backlog: Arc::new(StreamBacklog::default()),
+                config: None,
+                calibration: None,
+                triggered: None,
+                restore_configs: Vec::new(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Starts a LJM Stream as with `stream_start_addr`, first validating `config`
+    /// against the device type and writing its registers in a single batch.
+    /// Returns actual device scan rate (chosen by LabJack).
+    ///
+    /// The applied config is then available from `stream_config`.
+    ///
+    /// For triggered and externally clocked streams, reads wait for scans
+    /// without timing out, such that the first read returns once the trigger
+    /// has fired and `scans_per_read` scans have been received. Until then,
+    /// `stream_triggered` is unset. Scan times are taken from when it fired.
+    ///
+    /// For binary streams, the device's calibration constants are read
+    /// before the stream is started, and kept for `stream_calibration`.
+    ///
+    /// LJM's library configs are shared by every handle in the process, so
+    /// the receive timeout configs are only changed while the stream is started
+    /// (when LJM reads them), and are then restored. `LJM_STREAM_AIN_BINARY`
+    /// also applies to reads, so is kept until `stream_stop` restores it.
+    #[doc(alias = "LJM_eStreamStart")]
+    #[cfg(feature = "stream")]
+    pub fn stream_start_config(handle: i32, config: StreamConfig) -> Result<f64, LJMError> {
+        let device_type = LJMLibrary::get_handle_info(handle)?.device_type;
+        config.validate(&device_type)?;
+
+        LJMLibrary::write_names(handle, &config.registers())?;
+
+        let calibration = if config.is_binary() {
+            Some(CalibrationTable::read(handle)?)
+        } else {
+            None
+        };
+        let binary =
+            LJMLibrary::swap_configs(&[(LJM_STREAM_AIN_BINARY, config.is_binary() as i32 as f64)])?;
+        let previous = match LJMLibrary::swap_configs(&config.library_configs()) {
+            Ok(previous) => previous,
+            Err(error) => {
+                let _ = LJMLibrary::restore_configs(&binary);
+                return Err(error);
+            }
+        };
+
+        let started = LJMLibrary::stream_start_addr(
+            handle,
+            config.scans_per_read(),
+            config.scan_rate(),
+            config.channels().to_vec(),
+        );
+        let restored = LJMLibrary::restore_configs(&previous);
+
+        let scan_rate = match started {
+            Ok(scan_rate) => scan_rate,
+            Err(error) => {
+                let _ = LJMLibrary::restore_configs(&binary);
+                return Err(error);
+            }
+        };
+        restored?;
+
+        if let Some(stream) = LJM_WRAPPER
+            .get()
+            .ok_or(LJMError::Uninitialized)?
+            .stream
+            .write()
+            .map_err(|_| LJMError::PoisonedLock)?
+            .get_mut(&handle)
+        {
+            stream.triggered = config
+                .is_triggered()
+                .then(|| Arc::new(AtomicBool::new(false)));
+            stream.config = Some(config);
+            stream.calibration = calibration;
+            stream.restore_configs = binary;
+        }
+
+        Ok(scan_rate)
+    }
+
+    // Sets each library config in order, returning their previous values. If
+    // any cannot be set, those already set are restored.
+    #[cfg(feature = "stream")]
+    fn swap_configs<'a>(configs: &[(&'a str, f64)]) -> Result<Vec<(&'a str, f64)>, LJMError> {
+        let previous = configs
+            .iter()
+            .map(|(name, _)| Ok((*name, LJMLibrary::get_config(name.to_string())?)))
+            .collect::<Result<Vec<_>, LJMError>>()?;
+
+        for (set, (name, value)) in configs.iter().enumerate() {
+            if let Err(error) = LJMLibrary::set_config(*name, *value) {
+                let _ = LJMLibrary::restore_configs(&previous[..set]);
+                return Err(error);
+            }
+        }
+
+        Ok(previous)
+    }
+
+    // Restores the values returned by `swap_configs`, in the reverse of
+    // the order they were set (see `StreamConfig::library_configs`).
+    #[cfg(feature = "stream")]
+    fn restore_configs(previous: &[(&str, f64)]) -> Result<(), LJMError> {
+        previous
+            .iter()
+            .rev()
+            .try_for_each(|(name, value)| LJMLibrary::set_config(*name, *value))
+    }
+
+    /// The config applied to the handle's stream by `stream_start_config`, if any.
+    #[cfg(feature = "stream")]
+    pub fn stream_config(handle: i32) -> Result<Option<StreamConfig>, LJMError> {
+        let lock = LJM_WRAPPER
+            .get()
+            .ok_or(LJMError::Uninitialized)?
+            .stream
+            .read()
+            .map_err(|_| LJMError::PoisonedLock)?;
+
+        let stream_value = lock.get(&handle).ok_or(LJMError::StreamNotStarted)?;
+
+        Ok(stream_value.config.clone())
+    }
+
+    /// The calibration read when the handle's binary stream was started
+    /// by `stream_start_config`, if any.
+    #[cfg(feature = "stream")]
+    pub fn stream_calibration(handle: i32) -> Result<Option<CalibrationTable>, LJMError> {
+        let lock = LJM_WRAPPER
+            .get()
+            .ok_or(LJMError::Uninitialized)?
+            .stream
+            .read()
+            .map_err(|_| LJMError::PoisonedLock)?;
+
+        let stream_value = lock.get(&handle).ok_or(LJMError::StreamNotStarted)?;
+
+        Ok(stream_value.calibration.clone())
+    }
+
+    /// Opens a `Replay` as a virtual handle, which may be streamed from
+    /// with `stream_start` and `stream_read` in place of a device.
+    ///
+    /// Other functions (such as `read_name`) are not supported on virtual handles.
+    #[cfg(feature = "stream")]
+    pub fn open_replay(replay: Replay) -> Result<i32, LJMError> {
+        let handle = NEXT_REPLAY_HANDLE.fetch_sub(1, Ordering::Relaxed);
+
+        LJM_WRAPPER
+            .get()
+            .ok_or(LJMError::Uninitialized)?
+            .replay
+            .write()
+            .map_err(|_| LJMError::PoisonedLock)?
+            .insert(handle, Arc::new(Mutex::new(replay)));
+
+        Ok(handle)
+    }
+
+    /// Closes a virtual handle opened with `open_replay`, stopping any stream.
+    #[cfg(feature = "stream")]
+    pub fn close_replay(handle: i32) -> Result<Option<Replay>, LJMError> {
+        if LJMLibrary::is_stream_active(handle) {
+            LJMLibrary::stream_stop(handle)?;
+        }
+
+        let replay = LJM_WRAPPER
+            .get()
+            .ok_or(LJMError::Uninitialized)?
+            .replay
+            .write()
+            .map_err(|_| LJMError::PoisonedLock)?
+            .remove(&handle);
+
+        // Only a concurrent read of the replay still holds it.
+        replay
+            .map(|replay| {
+                Arc::try_unwrap(replay)
+                    .map_err(|_| LJMError::BadState)?
+                    .into_inner()
+                    .map_err(|_| LJMError::PoisonedLock)
+            })
+            .transpose()
+    }
+
+    // Applies `f` to the replay opened as `handle`, or returns `None` for other handles.
+    // Replays sleep while reading, so only the replay itself is locked while `f` runs.
+    #[cfg(feature = "stream")]
+    fn with_replay<T>(
+        handle: i32,
+        f: impl FnOnce(&mut Replay) -> Result<T, LJMError>,
+    ) -> Result<Option<T>, LJMError> {
+        let replay = LJM_WRAPPER
+            .get()
+            .ok_or(LJMError::Uninitialized)?
+            .replay
+            .read()
+            .map_err(|_| LJMError::PoisonedLock)?
+            .get(&handle)
+            .cloned();
+
+        match replay {
+            Some(replay) => f(&mut *replay.lock().map_err(|_| LJMError::PoisonedLock)?).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    #[cfg(feature = "stream")]
+    fn start_replay(handle: i32, addresses: &[i32]) -> Result<Option<f64>, LJMError> {
+        LJMLibrary::with_replay(handle, |replay| replay.start(addresses))
+    }
+
+    // Returns whether `handle` was a replay.
+    #[cfg(feature = "stream")]
+    fn stop_replay(handle: i32) -> Result<bool, LJMError> {
+        let stopped = LJMLibrary::with_replay(handle, |replay| {
+            replay.stop();
+            Ok(())
+        })?;
+
+        Ok(stopped.is_some())
+    }
+
+    /// Collects `num_scans` scans of the given addresses in a single blocking
+    /// call, starting and stopping the stream internally.
+    ///
+    /// As a burst is self-contained it does not register a stream for the
+    /// handle, and so cannot be read with `stream_read`. This blocks for
+    /// at least `num_scans / scan_rate` seconds.
+    #[doc(alias = "LJM_StreamBurst")]
+    #[cfg(feature = "stream")]
+    pub fn stream_burst(
+        handle: i32,
+        addresses: Vec<i32>,
+        suggested_scan_rate: f64,
+        num_scans: u32,
+    ) -> Result<Burst, LJMError> {
+        #[cfg(feature = "dynlink")]
+        let stream_burst: Symbol<
+            extern "C" fn(i32, i32, *const i32, *mut c_double, c_uint, *mut c_double) -> i32,
+        > = unsafe { LJMLibrary::get_c_function(b"LJM_StreamBurst")? };
+
+        let mut scan_rate: f64 = suggested_scan_rate;
+        let mut data = vec![0.0; num_scans as usize * addresses.len()];
+
+        #[cfg(feature = "dynlink")]
+        let error_code = stream_burst(
+            handle,
+            addresses.len() as i32,
+            addresses.as_ptr(),
+            &mut scan_rate,
+            num_scans,
+            data.as_mut_ptr(),
+        );
+        #[cfg(feature = "staticlink")]
+        let error_code = unsafe {
+            lib::LJM_StreamBurst(
+                handle,
+                addresses.len() as i32,
+                addresses.as_ptr(),
+                &mut scan_rate,
+                num_scans,
+                data.as_mut_ptr(),
+            )
+        };
+
+        let channels = deinterleave(&data, addresses.len());
+
+        LJMLibrary::error_code(
+            Burst {
+                scan_rate,
+                scan_list: addresses,
+                channels,
+            },
+            error_code,
+        )
+    }
+
+    /// Configures stream-out `stream_out_index` (0 to 3) to repeatedly write
+    /// `waveform` (one period) to the `target` register, such as `DAC0`.
+    /// Other indexes return `LJMError::InvalidStreamConfig`.
+    ///
+    /// The stream-out is added to the scan list of the next `stream_start`
+    /// on this handle, which should be called with the same `scan_rate`.
+    /// Waveforms may be created using `Waveform::generate`.
+    #[doc(alias = "LJM_PeriodicStreamOut")]
+    #[cfg(feature = "stream")]
+    pub fn periodic_stream_out<T: ToString>(
+        handle: i32,
+        stream_out_index: i32,
+        target: T,
+        scan_rate: f64,
+        waveform: &[f64],
+    ) -> Result<(), LJMError> {
+        let stream_out_address = LJMLibrary::stream_out_address(stream_out_index)?;
+
+        #[cfg(feature = "dynlink")]
+        let periodic_stream_out: Symbol<
+            extern "C" fn(i32, i32, i32, c_double, i32, *const c_double) -> i32,
+        > = unsafe { LJMLibrary::get_c_function(b"LJM_PeriodicStreamOut")? };
+
+        let (target_addr, _) = LJMLibrary::name_to_address(target)?;
+
+        #[cfg(feature = "dynlink")]
+        let error_code = periodic_stream_out(
+            handle,
+            stream_out_index,
+            target_addr,
+            scan_rate,
+            waveform.len() as i32,
+            waveform.as_ptr(),
+        );
+        #[cfg(feature = "staticlink")]
+        let error_code = unsafe {
+            lib::LJM_PeriodicStreamOut(
+                handle,
+                stream_out_index,
+                target_addr,
+                scan_rate,
+                waveform.len() as i32,
+                waveform.as_ptr(),
+            )
+        };
+
+        LJMLibrary::error_code((), error_code)?;
+        LJMLibrary::add_stream_out(handle, stream_out_address)
+    }
+
+    /// Configures stream-out `stream_out_index` (0 to 3) to write values
+    /// queued by `write_aperiodic_stream_out` to the `target` register.
     ///
-    #[doc(alias = "LJM_eStreamStart")]
+    /// As with `periodic_stream_out`, the stream-out is added to the scan list
+    /// of the next `stream_start` on this handle, which should use the same `scan_rate`.
+    #[doc(alias = "LJM_InitializeAperiodicStreamOut")]
     #[cfg(feature = "stream")]
-    pub fn stream_start_addr<T>(
+    pub fn initialize_aperiodic_stream_out<T: ToString>(
         handle: i32,
-        scans_per_read: i32,
-        suggested_scan_rate: f64,
-        streams: Vec<T>,
-    ) -> Result<f64, LJMError>
-    where
-        T: ToString + Display,
-    {
-        let addresses: Result<Vec<i32>, LJMError> =
-            streams.iter().try_fold(Vec::new(), |mut acc, a| {
-                let (address, _) = LJMLibrary::name_to_address(a)?;
-                acc.push(address);
-                Ok(acc)
-            });
+        stream_out_index: i32,
+        target: T,
+        scan_rate: f64,
+    ) -> Result<(), LJMError> {
+        let stream_out_address = LJMLibrary::stream_out_address(stream_out_index)?;
+
+        #[cfg(feature = "dynlink")]
+        let initialize_aperiodic_stream_out: Symbol<
+            extern "C" fn(i32, i32, i32, c_double) -> i32,
+        > = unsafe { LJMLibrary::get_c_function(b"LJM_InitializeAperiodicStreamOut")? };
+
+        let (target_addr, _) = LJMLibrary::name_to_address(target)?;
 
-        LJMLibrary::stream_start(handle, scans_per_read, suggested_scan_rate, addresses?)
+        #[cfg(feature = "dynlink")]
+        let error_code =
+            initialize_aperiodic_stream_out(handle, stream_out_index, target_addr, scan_rate);
+        #[cfg(feature = "staticlink")]
+        let error_code = unsafe {
+            lib::LJM_InitializeAperiodicStreamOut(handle, stream_out_index, target_addr, scan_rate)
+        };
+
+        LJMLibrary::error_code((), error_code)?;
+        LJMLibrary::add_stream_out(handle, stream_out_address)
     }
 
-    /// Starts a LJM Stream, stopped with `stream_stop`.
-    /// Returns actual device scan rate (chosen by LabJack)
+    /// Queues values to an aperiodic stream-out, returning the number
+    /// of values which can still be written to the queue.
     ///
-    /// `suggested_scan_rate` The scan rate forwarded to LJM which it will attempt to use
-    ///
-    #[doc(alias = "LJM_eStreamStart")]
+    /// Callers should wait for the queue to drain before writing
+    /// more values than remain, such as via `AperiodicStreamOut`.
+    #[doc(alias = "LJM_WriteAperiodicStreamOut")]
     #[cfg(feature = "stream")]
-    pub fn stream_start(
+    pub fn write_aperiodic_stream_out(
         handle: i32,
-        scans_per_read: i32,
-        suggested_scan_rate: f64,
-        addresses: Vec<i32>,
-    ) -> Result<f64, LJMError> {
+        stream_out_index: i32,
+        values: &[f64],
+    ) -> Result<i32, LJMError> {
+        LJMLibrary::stream_out_address(stream_out_index)?;
+
         #[cfg(feature = "dynlink")]
-        let stream_start: Symbol<
-            extern "C" fn(i32, i32, i32, *const i32, *mut c_double) -> i32,
-        > = unsafe { LJMLibrary::get_c_function(b"LJM_eStreamStart")? };
+        let write_aperiodic_stream_out: Symbol<
+            extern "C" fn(i32, i32, i32, *const c_double, *mut i32) -> i32,
+        > = unsafe { LJMLibrary::get_c_function(b"LJM_WriteAperiodicStreamOut")? };
 
-        let addr_slice: &[i32] = &addresses;
-        let mut scan_rate: f64 = suggested_scan_rate;
+        let mut queue_vals_remaining: i32 = 0;
 
         #[cfg(feature = "dynlink")]
-        let error_code = stream_start(
+        let error_code = write_aperiodic_stream_out(
             handle,
-            scans_per_read,
-            addresses.len() as i32,
-            addr_slice.as_ptr(),
-            &mut scan_rate,
+            stream_out_index,
+            values.len() as i32,
+            values.as_ptr(),
+            &mut queue_vals_remaining,
         );
         #[cfg(feature = "staticlink")]
         let error_code = unsafe {
-            lib::LJM_eStreamStart(
+            lib::LJM_WriteAperiodicStreamOut(
                 handle,
-                scans_per_read,
-                addresses.len() as i32,
-                addr_slice.as_ptr(),
-                &mut scan_rate,
+                stream_out_index,
+                values.len() as i32,
+                values.as_ptr(),
+                &mut queue_vals_remaining,
             )
         };
 
-        // If we don't have an error we will initialize the stream
-        if error_code == 0 {
-            let wrapper = LJM_WRAPPER.get();
+        LJMLibrary::error_code(queue_vals_remaining, error_code)
+    }
 
-            let mut stream = wrapper
-                .ok_or(LJMError::Uninitialized)?
-                .stream
-                .write()
-                .map_err(|_| LJMError::PoisonedLock)?;
+    // The address of `STREAM_OUT{index}`, of which each device has four.
+    #[cfg(feature = "stream")]
+    fn stream_out_address(stream_out_index: i32) -> Result<i32, LJMError> {
+        if !(0..NUM_STREAM_OUTS).contains(&stream_out_index) {
+            return Err(LJMError::InvalidStreamConfig(format!(
+                "stream-out index {} is not within 0 to {}",
+                stream_out_index,
+                NUM_STREAM_OUTS - 1
+            )));
+        }
 
-            stream.insert(
-                handle,
-                LJMStream {
-                    scan_list: addresses,
-                    scans_per_read,
-                },
-            );
+        Ok(STREAM_OUT_ADDRESS + stream_out_index)
+    }
+
+    /// The name of a stream-out's channel in stream data started by
+    /// `stream_start`, such as `STREAM_OUT1` for address 4801, or `None`
+    /// if `address` is not that of a stream-out.
+    #[cfg(feature = "stream")]
+    pub fn stream_out_name(address: i32) -> Option<String> {
+        let stream_out_index = address - STREAM_OUT_ADDRESS;
+        (0..NUM_STREAM_OUTS)
+            .contains(&stream_out_index)
+            .then(|| format!("STREAM_OUT{}", stream_out_index))
+    }
+
+    // Records a stream-out to be added to the handle's next stream.
+    #[cfg(feature = "stream")]
+    fn add_stream_out(handle: i32, address: i32) -> Result<(), LJMError> {
+        let mut stream_out = LJM_WRAPPER
+            .get()
+            .ok_or(LJMError::Uninitialized)?
+            .stream_out
+            .write()
+            .map_err(|_| LJMError::PoisonedLock)?;
+
+        let addresses = stream_out.entry(handle).or_default();
+        if !addresses.contains(&address) {
+            addresses.push(address);
         }
 
-        LJMLibrary::error_code(scan_rate, error_code)
+        Ok(())
     }
 
     /// Stops an LJM Stream started with `stream_start`, returns the stream
@@ -633,40 +2224,330 @@ impl LJMLibrary {
     #[doc(alias = "LJM_eStreamStop")]
     #[cfg(feature = "stream")]
     pub fn stream_stop(handle: i32) -> Result<Option<LJMStream>, LJMError> {
+        if LJMLibrary::stop_replay(handle)? {
+            return LJMLibrary::remove_stream(handle);
+        }
+
         #[cfg(feature = "dynlink")]
         let stream_stop: Symbol<extern "C" fn(i32) -> i32> =
             unsafe { LJMLibrary::get_c_function(b"LJM_eStreamStop")? };
 
+        // LJM must stop using the callback before we release it. The stream is
+        // stopped regardless, after which LJM no longer invokes any callback.
+        let cleared = match LJMLibrary::has_stream_callback(handle) {
+            Ok(true) => LJMLibrary::e_set_stream_callback(handle, None, std::ptr::null_mut())
+                .and_then(|error_code| LJMLibrary::error_code((), error_code)),
+            Ok(false) => Ok(()),
+            Err(error) => Err(error),
+        };
+
         #[cfg(feature = "dynlink")]
         let error_code = stream_stop(handle);
         #[cfg(feature = "staticlink")]
         let error_code = unsafe { lib::LJM_eStreamStop(handle) };
 
         // Remove stream from active
-        let stream = LJM_WRAPPER
+        let stream = LJMLibrary::remove_stream(handle);
+        let restored = match &stream {
+            Ok(Some(stream)) => LJMLibrary::restore_configs(&stream.restore_configs),
+            _ => Ok(()),
+        };
+
+        cleared?;
+        let stream = LJMLibrary::error_code(stream?, error_code)?;
+        restored?;
+
+        Ok(stream)
+    }
+
+    #[cfg(feature = "stream")]
+    fn remove_stream(handle: i32) -> Result<Option<LJMStream>, LJMError> {
+        Ok(LJM_WRAPPER
             .get()
             .ok_or(LJMError::Uninitialized)?
             .stream
             .write()
             .map_err(|e| LJMError::LibraryError(e.to_string()))?
-            .remove(&handle);
-
-        LJMLibrary::error_code(stream, error_code)
+            .remove(&handle))
     }
 
     /// Stops an LJM Stream started with `stream_start`
     #[doc(alias = "LJM_eStreamRead")]
     #[cfg(feature = "stream")]
     pub fn stream_read(handle: i32) -> Result<Vec<f64>, LJMError> {
+        LJMLibrary::stream_read_detailed(handle).map(|block| block.data)
+    }
+
+    /// Reads from an LJM Stream as with `stream_read`, also returning the
+    /// scan backlogs and whether any scans were lost or skipped, so callers
+    /// can detect a stream falling behind before the buffer overflows.
+    ///
+    /// With `LJM_STREAM_SCANS_RETURN` set to `LJM_STREAM_SCANS_RETURN_ALL_OR_NONE`,
+    /// returns an empty block until enough scans are received.
+    #[doc(alias = "LJM_eStreamRead")]
+    #[cfg(feature = "stream")]
+    pub fn stream_read_detailed(handle: i32) -> Result<StreamBlock, LJMError> {
+        let (scans_per_read, num_channels) = LJMLibrary::stream_shape(handle)?;
+
+        // Length = ScansPerRead * NumberOfAddresses
+        let mut addr_slice = vec![0.0; scans_per_read * num_channels];
+        let (dev_scan_backlog, ljm_scan_backlog, timing) =
+            match LJMLibrary::read_stream(handle, &mut addr_slice, true) {
+                Err(LJMError::ErrorCode(code, _, _))
+                    if i32::from(&code) == LJME_NO_SCANS_RETURNED =>
+                {
+                    return Ok(StreamBlock::empty(LJMLibrary::stream_triggered(handle)?));
+                }
+                read => read?,
+            };
+
+        Ok(StreamBlock::new(
+            addr_slice,
+            num_channels,
+            dev_scan_backlog,
+            ljm_scan_backlog,
+            Some(timing),
+        ))
+    }
+
+    /// Reads from a binary stream (see `StreamConfig::binary`) as with
+    /// `stream_read`, returning the raw 16-bit counts of each value, which
+    /// may be converted to volts with the handle's `stream_calibration`.
+    ///
+    /// Only streams of AIN channels are returned as counts, so streams with
+    /// other channels (such as stream-outs) are rejected. Values which do
+    /// not fit in 16 bits, such as `LJM_DUMMY_VALUE`, are returned as `u16::MAX`,
+    /// while values which are not whole counts (such as volts, if
+    /// `LJM_STREAM_AIN_BINARY` was changed by another caller) are an error.
+    #[doc(alias = "LJM_eStreamRead")]
+    #[cfg(feature = "stream")]
+    pub fn stream_read_raw(handle: i32) -> Result<Vec<u16>, LJMError> {
+        if !LJMLibrary::stream_config(handle)?.is_some_and(|config| config.is_binary()) {
+            return Err(LJMError::InvalidStreamConfig(
+                "stream was not started in binary mode".to_string(),
+            ));
+        }
+
+        let (names, _) = LJMLibrary::stream_channels(handle)?;
+        if let Some(name) = names.iter().find(|name| !is_ain_channel(name)) {
+            return Err(LJMError::InvalidStreamConfig(format!(
+                "{} is not an AIN channel, so cannot be read as counts",
+                name
+            )));
+        }
+
+        let data = LJMLibrary::stream_read(handle)?;
+
+        data.into_iter()
+            .map(|value| {
+                if value.fract() != 0.0 {
+                    Err(LJMError::InvalidStreamConfig(format!(
+                        "{} was read, rather than a count",
+                        value
+                    )))
+                } else if (0.0..=u16::MAX as f64).contains(&value) {
+                    Ok(value as u16)
+                } else {
+                    Ok(u16::MAX)
+                }
+            })
+            .collect()
+    }
+
+    /// Reads from an LJM Stream as with `stream_read_detailed`, returning
+    /// a `StreamFrame` which may be indexed by channel name or address.
+    #[doc(alias = "LJM_eStreamRead")]
+    #[cfg(feature = "stream")]
+    pub fn stream_read_frame(handle: i32) -> Result<StreamFrame, LJMError> {
+        let (names, addresses) = LJMLibrary::stream_channels(handle)?;
+        let block = LJMLibrary::stream_read_detailed(handle)?;
+
+        Ok(StreamFrame::new(block, names, addresses))
+    }
+
+    /// Reads from an LJM Stream on a blocking thread, yielding each block
+    /// to async consumers through an `AsyncStream`, which buffers up to
+    /// `capacity` blocks.
+    ///
+    /// Must be called from within a tokio runtime.
+    #[doc(alias = "LJM_eStreamRead")]
+    #[cfg(all(feature = "stream", feature = "tokio"))]
+    pub fn stream_async(handle: i32, capacity: usize) -> Result<AsyncStream, LJMError> {
+        // Fail early, rather than yielding the error from the stream.
+        LJMLibrary::stream_shape(handle)?;
+
+        let (sender, receiver) = tokio::sync::mpsc::channel(capacity.max(1));
+
+        tokio::task::spawn_blocking(move || loop {
+            let block = LJMLibrary::stream_read_detailed(handle);
+            let failed = block.is_err();
+
+            // The consumer has dropped the stream, or reading has failed.
+            if sender.blocking_send(block).is_err() || failed {
+                break;
+            }
+        });
+
+        Ok(AsyncStream::new(handle, receiver))
+    }
+
+    /// Reads from an LJM Stream directly into `buffer`, without allocating.
+    /// Returns the `(device, LJM)` scan backlogs.
+    ///
+    /// The buffer must hold exactly `ScansPerRead * NumberOfAddresses` values,
+    /// otherwise `LJMError::BufferSizeMismatch` is returned. See `StreamBuffer`
+    /// for a buffer sized to the handle's stream.
+    ///
+    /// With `LJM_STREAM_SCANS_RETURN` set to `LJM_STREAM_SCANS_RETURN_ALL_OR_NONE`,
+    /// returns an `LJMError::ErrorCode` of `LJME_NO_SCANS_RETURNED` (1309)
+    /// until enough scans are received.
+    #[doc(alias = "LJM_eStreamRead")]
+    #[cfg(feature = "stream")]
+    pub fn stream_read_into(handle: i32, buffer: &mut [f64]) -> Result<(i32, i32), LJMError> {
+        LJMLibrary::read_stream(handle, buffer, false)
+            .map(|(dev_scan_backlog, ljm_scan_backlog, _)| (dev_scan_backlog, ljm_scan_backlog))
+    }
+
+    // Reads into `buffer`, advancing the stream's clock (collecting
+    // measured scan times only if `measure` is set).
+    #[cfg(feature = "stream")]
+    fn read_stream(
+        handle: i32,
+        buffer: &mut [f64],
+        measure: bool,
+    ) -> Result<(i32, i32, StreamTiming), LJMError> {
+        // Reads may wait indefinitely (such as for a trigger), so the stream's
+        // state is taken from the map rather than holding its lock, which
+        // would block streams starting or stopping on other handles.
+        let (scans_per_read, scan_length, layout, capture_buffer, backlog, clock, triggered) = {
+            let lock = LJM_WRAPPER
+                .get()
+                .ok_or(LJMError::Uninitialized)?
+                .stream
+                .read()
+                .map_err(|_| LJMError::PoisonedLock)?;
+
+            let stream_value = lock.get(&handle).ok_or(LJMError::StreamNotStarted)?;
+            let scans_per_read = stream_value.scans_per_read as usize;
+
+            (
+                scans_per_read,
+                // Length = ScansPerRead * NumberOfAddresses
+                scans_per_read * stream_value.scan_list.len(),
+                stream_value.layout.clone(),
+                stream_value.capture_buffer.clone(),
+                stream_value.backlog.clone(),
+                stream_value.clock.clone(),
+                stream_value.triggered.clone(),
+            )
+        };
+
+        if buffer.len() != scan_length {
+            return Err(LJMError::BufferSizeMismatch(scan_length, buffer.len()));
+        }
+
+        let (dev_scan_backlog, ljm_scan_backlog) =
+            match LJMLibrary::with_replay(handle, |replay| replay.read(buffer))? {
+                Some(backlogs) => backlogs,
+                None if layout.is_empty() => LJMLibrary::e_stream_read(handle, buffer)?,
+                None => {
+                    let mut physical = capture_buffer.lock().map_err(|_| LJMError::PoisonedLock)?;
+                    physical.resize(scans_per_read * layout.physical_len(), 0.0);
+
+                    let backlogs = LJMLibrary::e_stream_read(handle, &mut physical)?;
+                    layout.combine(&physical, buffer);
+                    backlogs
+                }
+            };
+
+        backlog.record(dev_scan_backlog, ljm_scan_backlog);
+
+        // The trigger fired as the first of the scans read, or of those
+        // still backlogged, was taken.
+        if let Some(triggered) = &triggered {
+            if !triggered.swap(true, Ordering::Relaxed) {
+                let backlog = dev_scan_backlog.max(0) + ljm_scan_backlog.max(0);
+                clock.rebase((scans_per_read + backlog as usize) as u64);
+            }
+        }
+
+        let timing = clock.advance(buffer, measure);
+
+        Ok((dev_scan_backlog, ljm_scan_backlog, timing))
+    }
+
+    // Returns the (names, addresses) of the channels in the handle's stream.
+    #[cfg(feature = "stream")]
+    pub(crate) fn stream_channels(handle: i32) -> Result<(Vec<String>, Vec<i32>), LJMError> {
+        let lock = LJM_WRAPPER
+            .get()
+            .ok_or(LJMError::Uninitialized)?
+            .stream
+            .read()
+            .map_err(|_| LJMError::PoisonedLock)?;
+
+        let stream_value = lock.get(&handle).ok_or(LJMError::StreamNotStarted)?;
+
+        Ok((stream_value.names.clone(), stream_value.scan_list.clone()))
+    }
+
+    /// Whether the handle's stream has received scans since its trigger fired.
+    /// Always set for streams started without a trigger.
+    #[cfg(feature = "stream")]
+    pub fn stream_triggered(handle: i32) -> Result<bool, LJMError> {
+        let lock = LJM_WRAPPER
+            .get()
+            .ok_or(LJMError::Uninitialized)?
+            .stream
+            .read()
+            .map_err(|_| LJMError::PoisonedLock)?;
+
+        let stream_value = lock.get(&handle).ok_or(LJMError::StreamNotStarted)?;
+
+        Ok(stream_value
+            .triggered
+            .as_ref()
+            .is_none_or(|triggered| triggered.load(Ordering::Relaxed)))
+    }
+
+    // Returns the clock of the handle's stream.
+    #[cfg(feature = "stream")]
+    pub(crate) fn stream_clock(handle: i32) -> Result<Arc<StreamClock>, LJMError> {
+        let lock = LJM_WRAPPER
+            .get()
+            .ok_or(LJMError::Uninitialized)?
+            .stream
+            .read()
+            .map_err(|_| LJMError::PoisonedLock)?;
+
+        let stream_value = lock.get(&handle).ok_or(LJMError::StreamNotStarted)?;
+
+        Ok(stream_value.clock.clone())
+    }
+
+    // Returns the (scans_per_read, number of channels) of the handle's stream.
+    #[cfg(feature = "stream")]
+    pub(crate) fn stream_shape(handle: i32) -> Result<(usize, usize), LJMError> {
         let lock = LJM_WRAPPER
             .get()
             .ok_or(LJMError::Uninitialized)?
             .stream
             .read()
-            .map_err(|_| LJMError::StreamNotStarted)?;
+            .map_err(|_| LJMError::PoisonedLock)?;
 
         let stream_value = lock.get(&handle).ok_or(LJMError::StreamNotStarted)?;
 
+        Ok((
+            stream_value.scans_per_read as usize,
+            stream_value.scan_list.len(),
+        ))
+    }
+
+    // Reads a single block of stream data into `buffer`, which must hold
+    // `ScansPerRead * NumberOfAddresses` values. Returns the device and LJM
+    // scan backlogs.
+    #[cfg(feature = "stream")]
+    pub(crate) fn e_stream_read(handle: i32, buffer: &mut [f64]) -> Result<(i32, i32), LJMError> {
         #[cfg(feature = "dynlink")]
         let stream_read: Symbol<extern "C" fn(i32, *mut f64, *mut i32, *mut i32) -> i32> =
             unsafe { LJMLibrary::get_c_function(b"LJM_eStreamRead")? };
@@ -674,15 +2555,10 @@ impl LJMLibrary {
         let mut dev_scan_backlog: i32 = 0;
         let mut ljm_scan_backlog: i32 = 0;
 
-        // Length = ScansPerRead * NumberOfAddresses
-        let scan_length = stream_value.scans_per_read as usize * stream_value.scan_list.len();
-
-        let mut addr_slice = vec![0.0; scan_length];
-
         #[cfg(feature = "dynlink")]
         let error_code = stream_read(
             handle,
-            addr_slice.as_mut_ptr(),
+            buffer.as_mut_ptr(),
             &mut dev_scan_backlog,
             &mut ljm_scan_backlog,
         );
@@ -690,13 +2566,145 @@ impl LJMLibrary {
         let error_code = unsafe {
             lib::LJM_eStreamRead(
                 handle,
-                addr_slice.as_mut_ptr(),
+                buffer.as_mut_ptr(),
                 &mut dev_scan_backlog,
                 &mut ljm_scan_backlog,
             )
         };
 
-        LJMLibrary::error_code(addr_slice, error_code)
+        LJMLibrary::error_code((dev_scan_backlog, ljm_scan_backlog), error_code)
+    }
+
+    /// Returns `(buffer_size, bytes_in_buffer)` for the TCP receive buffer
+    /// of a stream running over Ethernet or WiFi. A buffer approaching full
+    /// indicates data is not being read quickly enough, and will be lost.
+    #[doc(alias = "LJM_GetStreamTCPReceiveBufferStatus")]
+    #[cfg(feature = "stream")]
+    pub fn stream_tcp_buffer_status(handle: i32) -> Result<(u32, u32), LJMError> {
+        #[cfg(feature = "dynlink")]
+        let get_buffer_status: Symbol<extern "C" fn(i32, *mut c_uint, *mut c_uint) -> i32> =
+            unsafe { LJMLibrary::get_c_function(b"LJM_GetStreamTCPReceiveBufferStatus")? };
+
+        let mut buffer_size: c_uint = 0;
+        let mut bytes_in_buffer: c_uint = 0;
+
+        #[cfg(feature = "dynlink")]
+        let error_code = get_buffer_status(handle, &mut buffer_size, &mut bytes_in_buffer);
+        #[cfg(feature = "staticlink")]
+        let error_code = unsafe {
+            lib::LJM_GetStreamTCPReceiveBufferStatus(handle, &mut buffer_size, &mut bytes_in_buffer)
+        };
+
+        LJMLibrary::error_code((buffer_size, bytes_in_buffer), error_code)
+    }
+
+    /// Reports the scan backlogs from the most recent read of the handle's
+    /// stream, alongside the TCP receive buffer status for network connections.
+    #[cfg(feature = "stream")]
+    pub fn stream_status(handle: i32) -> Result<StreamStatus, LJMError> {
+        let (dev_scan_backlog, ljm_scan_backlog) = LJM_WRAPPER
+            .get()
+            .ok_or(LJMError::Uninitialized)?
+            .stream
+            .read()
+            .map_err(|_| LJMError::PoisonedLock)?
+            .get(&handle)
+            .ok_or(LJMError::StreamNotStarted)?
+            .backlog
+            .get();
+
+        let tcp_receive_buffer = match LJMLibrary::get_handle_info(handle)?.connection_type {
+            ConnectionType::ETHERNET | ConnectionType::WIFI => {
+                Some(LJMLibrary::stream_tcp_buffer_status(handle)?)
+            }
+            _ => None,
+        };
+
+        Ok(StreamStatus {
+            dev_scan_backlog,
+            ljm_scan_backlog,
+            tcp_receive_buffer,
+        })
+    }
+
+    /// Registers a callback which LJM invokes from its own stream thread
+    /// each time `scans_per_read` scans are available, as an alternative
+    /// to polling `stream_read`. Must be called after `stream_start`,
+    /// replacing any previously registered callback.
+    ///
+    /// The callback is owned by the handle's stream and dropped by `stream_stop`,
+    /// as are any callbacks it replaced, which LJM's stream thread may still be running.
+    /// Reads which fail (such as on a buffer overflow) are not passed to the callback.
+    #[doc(alias = "LJM_SetStreamCallback")]
+    #[cfg(feature = "stream")]
+    pub fn set_stream_callback<F>(handle: i32, callback: F) -> Result<(), LJMError>
+    where
+        F: FnMut(StreamBlock) + Send + 'static,
+    {
+        // Held until LJM has the callback, so the stream cannot be removed
+        // (dropping the context) while LJM may use it.
+        let mut lock = LJM_WRAPPER
+            .get()
+            .ok_or(LJMError::Uninitialized)?
+            .stream
+            .write()
+            .map_err(|_| LJMError::PoisonedLock)?;
+
+        let stream = lock.get_mut(&handle).ok_or(LJMError::StreamNotStarted)?;
+
+        let context = Arc::new(StreamCallback {
+            handle,
+            scans_per_read: stream.scans_per_read as usize,
+            num_channels: stream.scan_list.len(),
+            layout: stream.layout.clone(),
+            callback: Mutex::new(Box::new(callback)),
+            backlog: stream.backlog.clone(),
+            clock: stream.clock.clone(),
+        });
+
+        // The stream owns the context before LJM is given it. Should registering
+        // fail, it is unknown which context LJM holds, so all are kept.
+        if let Some(previous) = stream.callback.replace(context.clone()) {
+            stream.retired_callbacks.push(previous);
+        }
+
+        let error_code = LJMLibrary::e_set_stream_callback(
+            handle,
+            Some(stream_read_callback),
+            Arc::as_ptr(&context) as *mut c_void,
+        )?;
+        LJMLibrary::error_code((), error_code)
+    }
+
+    #[cfg(feature = "stream")]
+    fn has_stream_callback(handle: i32) -> Result<bool, LJMError> {
+        Ok(LJM_WRAPPER
+            .get()
+            .ok_or(LJMError::Uninitialized)?
+            .stream
+            .read()
+            .map_err(|_| LJMError::PoisonedLock)?
+            .get(&handle)
+            .is_some_and(|stream| stream.callback.is_some()))
+    }
+
+    #[cfg(feature = "stream")]
+    fn e_set_stream_callback(
+        handle: i32,
+        callback: LJMStreamReadCallback,
+        arg: *mut c_void,
+    ) -> Result<i32, LJMError> {
+        #[cfg(feature = "dynlink")]
+        let set_stream_callback: Symbol<
+            extern "C" fn(i32, LJMStreamReadCallback, *mut c_void) -> i32,
+        > = unsafe { LJMLibrary::get_c_function(b"LJM_SetStreamCallback")? };
+
+        #[cfg(feature = "dynlink")]
+        let error_code = set_stream_callback(handle, callback, arg);
+        #[cfg(feature = "staticlink")]
+        let error_code = unsafe { lib::LJM_SetStreamCallback(handle, callback, arg) };
+
+        Ok(error_code)
     }
 
     /// Digitally writes an integer config
@@ -71,8 +71,11 @@ pub enum LJMError {
     #[cfg(feature = "dynlink")]
     LibloadingError(libloading::Error),
 
-    WrapperInvalid(LJMLibrary),
-    ErrorCode(LJMErrorCode, String),
+    // Boxed, as the stream state held by the library would
+    // otherwise make every `Result<_, LJMError>` very large.
+    WrapperInvalid(Box<LJMLibrary>),
+    // The frame which caused the error, for multi-frame calls which report one.
+    ErrorCode(LJMErrorCode, String, Option<ErrorFrame>),
     LibraryError(String),
 
     Uninitialized,
@@ -84,6 +87,35 @@ pub enum LJMError {
 
     PoisonedLock,
     CStringConversionFailed,
+    // A string register value which was too long, or not valid UTF-8.
+    InvalidString(String),
+    // A buffer of the wrong length for the stream, as (expected, actual).
+    BufferSizeMismatch(usize, usize),
+
+    IoError(std::io::Error),
+    // A stream recording which could not be parsed.
+    InvalidRecording(String),
+    // A replayed stream has served every recorded scan.
+    EndOfRecording,
+    // A stream configuration which the device does not support.
+    InvalidStreamConfig(String),
+    // Calibration constants which could not be read or parsed.
+    InvalidCalibration(String),
+}
+
+/// The frame of a multi-frame call (such as `read_names`) which caused an error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ErrorFrame {
+    /// The address LJM reported as causing the error.
+    pub address: i32,
+    /// The index of the frame (or array value) with that address in the request, if found.
+    pub index: Option<usize>,
+}
+
+impl From<std::io::Error> for LJMError {
+    fn from(value: std::io::Error) -> Self {
+        LJMError::IoError(value)
+    }
 }
 
 #[cfg(feature = "dynlink")]
@@ -104,7 +136,27 @@ impl Debug for LJMError {
                 #[cfg(feature = "dynlink")]
                 LJMError::LibloadingError(error) => format!("LibraryLoadingError::{:?}", error),
 
-                LJMError::ErrorCode(error, value) => format!("LJMError::{:?} ({})", error, value),
+                LJMError::ErrorCode(error, value, None) =>
+                    format!("LJMError::{:?} ({})", error, value),
+                LJMError::ErrorCode(
+                    error,
+                    value,
+                    Some(ErrorFrame {
+                        address,
+                        index: Some(frame),
+                    }),
+                ) => format!(
+                    "LJMError::{:?} ({}) at address {} (frame {})",
+                    error, value, address, frame
+                ),
+                LJMError::ErrorCode(
+                    error,
+                    value,
+                    Some(ErrorFrame {
+                        address,
+                        index: None,
+                    }),
+                ) => format!("LJMError::{:?} ({}) at address {}", error, value, address),
                 LJMError::LibraryError(error) => format!("LibraryError::{:?}", error),
                 LJMError::WrapperInvalid(_) => "WrapperInvalidError".to_string(),
                 LJMError::BadState => "BadStateError".to_string(),
@@ -116,6 +168,19 @@ impl Debug for LJMError {
                 LJMError::ScriptNotSet => "ScriptNotSetError".to_string(),
                 LJMError::PoisonedLock => "PoisonedLockError".to_string(),
                 LJMError::CStringConversionFailed => "CStringConversionFailed".to_string(),
+                LJMError::InvalidString(reason) => format!("InvalidStringError::{:?}", reason),
+                LJMError::BufferSizeMismatch(expected, actual) => format!(
+                    "BufferSizeMismatchError (expected {}, got {})",
+                    expected, actual
+                ),
+                LJMError::IoError(error) => format!("IoError::{:?}", error),
+                LJMError::InvalidRecording(reason) =>
+                    format!("InvalidRecordingError::{:?}", reason),
+                LJMError::EndOfRecording => "EndOfRecordingError".to_string(),
+                LJMError::InvalidStreamConfig(reason) =>
+                    format!("InvalidStreamConfigError::{:?}", reason),
+                LJMError::InvalidCalibration(reason) =>
+                    format!("InvalidCalibrationError::{:?}", reason),
             }
         )
     }
@@ -1,8 +1,10 @@
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
+use std::net::Ipv4Addr;
 
-#[derive(Clone, Copy, Debug, Serialize, Deserialize, Default)]
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum DeviceType {
     T4,
     T7,
@@ -28,7 +30,22 @@ impl From<i32> for DeviceType {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+impl From<&DeviceType> for i32 {
+    fn from(value: &DeviceType) -> Self {
+        match value {
+            DeviceType::T4 => 4,
+            DeviceType::T7 => 7,
+            DeviceType::T8 => 8,
+            DeviceType::TSERIES => 84,
+            DeviceType::DIGIT => 200,
+            DeviceType::ANY => 0,
+            DeviceType::EMULATED(value) | DeviceType::UNKNOWN(value) => *value,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ConnectionType {
     USB,
     ETHERNET,
@@ -48,7 +65,20 @@ impl From<i32> for ConnectionType {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+impl From<&ConnectionType> for i32 {
+    fn from(value: &ConnectionType) -> Self {
+        match value {
+            ConnectionType::USB => 1,
+            ConnectionType::ETHERNET => 3,
+            ConnectionType::WIFI => 4,
+            ConnectionType::ANY => 0,
+            ConnectionType::UNKNOWN(value) => *value,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DeviceHandleInfo {
     pub device_type: DeviceType,
     pub connection_type: ConnectionType,
@@ -75,6 +105,95 @@ impl Display for DeviceHandleInfo {
     }
 }
 
+/// A device found by `LJMLibrary::list_all`, which can
+/// then be opened using its `serial_number` as the identifier.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DiscoveredDevice {
+    pub device_type: DeviceType,
+    pub connection_type: ConnectionType,
+    pub serial_number: i32,
+
+    /// Only set for TCP-based connections, otherwise `0.0.0.0`.
+    pub ip_address: Ipv4Addr,
+}
+
+impl Display for DiscoveredDevice {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // DT on CT @ 000.000.000.000 => SERIAL_NUMBER
+        write!(
+            f,
+            "{} on {} @ {} => {}",
+            self.device_type, self.connection_type, self.ip_address, self.serial_number
+        )
+    }
+}
+
+/// A device found by `LJMLibrary::list_all_extended`, alongside
+/// the registers which were queried from it during discovery.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ExtendedDiscoveredDevice {
+    pub device: DiscoveredDevice,
+    pub registers: Vec<DiscoveredRegister>,
+}
+
+impl ExtendedDiscoveredDevice {
+    /// Finds a queried register by the name it was requested with.
+    pub fn register(&self, name: &str) -> Option<&DiscoveredRegister> {
+        self.registers.iter().find(|register| register.name == name)
+    }
+}
+
+/// The raw (big-endian) bytes of a register queried during discovery.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DiscoveredRegister {
+    pub name: String,
+    pub address: i32,
+    pub data_type: i32,
+    pub bytes: Vec<u8>,
+}
+
+impl DiscoveredRegister {
+    /// The number of 16-bit registers spanned by a value of the given
+    /// LJM data type, or `None` if the type has no fixed size (`LJM_BYTE`).
+    pub fn registers_for_type(data_type: i32) -> Option<i32> {
+        match data_type {
+            0 => Some(1),       // LJM_UINT16
+            1..=3 => Some(2),   // LJM_UINT32, LJM_INT32, LJM_FLOAT32
+            98 => Some(50 / 2), // LJM_STRING (LJM_STRING_ALLOCATION_SIZE)
+            _ => None,
+        }
+    }
+
+    /// Decodes the register as a number, following its LJM data type.
+    /// Returns `None` for string and byte registers.
+    pub fn as_f64(&self) -> Option<f64> {
+        let bytes = self.bytes.as_slice();
+
+        match self.data_type {
+            0 => Some(u16::from_be_bytes(bytes.get(..2)?.try_into().ok()?) as f64),
+            1 => Some(u32::from_be_bytes(bytes.get(..4)?.try_into().ok()?) as f64),
+            2 => Some(i32::from_be_bytes(bytes.get(..4)?.try_into().ok()?) as f64),
+            3 => Some(f32::from_be_bytes(bytes.get(..4)?.try_into().ok()?) as f64),
+            _ => None,
+        }
+    }
+
+    /// Decodes the register as a null-terminated string.
+    /// Returns `None` if the bytes are not valid UTF-8.
+    pub fn as_string(&self) -> Option<String> {
+        let end = self
+            .bytes
+            .iter()
+            .position(|b| *b == 0)
+            .unwrap_or(self.bytes.len());
+
+        String::from_utf8(self.bytes[..end].to_vec()).ok()
+    }
+}
+
 impl Display for DeviceType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let str = match self {
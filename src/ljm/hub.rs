@@ -0,0 +1,387 @@
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use crate::{LJMError, LJMLibrary, StreamBlock};
+
+/// What a `StreamHub` does with a block for a subscriber whose queue is full.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SlowConsumerPolicy {
+    /// Discards the oldest queued block to make room.
+    #[default]
+    DropOldest,
+    /// Waits for the subscriber to make room, pausing every subscriber.
+    /// Scans then accumulate in the LJM buffer, as with a slow reader.
+    Block,
+    /// Disconnects the subscriber, which may still receive the queued blocks.
+    Disconnect,
+}
+
+/// How far a subscriber has kept up with its `StreamHub`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SubscriberStats {
+    /// Blocks queued for the subscriber.
+    pub published: u64,
+    /// Blocks taken from the queue by the subscriber.
+    pub received: u64,
+    /// Blocks discarded as the subscriber was too slow.
+    pub dropped: u64,
+    /// Blocks currently queued, waiting to be received.
+    pub lag: usize,
+    /// The most blocks which have been queued at once.
+    pub max_lag: usize,
+}
+
+#[derive(Debug, Default)]
+struct SubscriberState {
+    queue: VecDeque<Arc<StreamBlock>>,
+    stats: SubscriberStats,
+    // No more blocks will be published to the subscriber.
+    closed: bool,
+    // The `Subscription` has been dropped.
+    dropped: bool,
+}
+
+#[derive(Debug)]
+struct Subscriber {
+    capacity: usize,
+    policy: SlowConsumerPolicy,
+    state: Mutex<SubscriberState>,
+    // Notified whenever the queue or its state changes.
+    changed: Condvar,
+}
+
+impl Subscriber {
+    // The state is left consistent by every lock holder, so may be used after a panic.
+    fn state(&self) -> MutexGuard<'_, SubscriberState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn publish(&self, block: &Arc<StreamBlock>, running: &AtomicBool) {
+        let mut state = self.state();
+
+        if self.policy == SlowConsumerPolicy::Block {
+            while state.queue.len() >= self.capacity
+                && !state.dropped
+                && running.load(Ordering::Relaxed)
+            {
+                state = self
+                    .changed
+                    .wait(state)
+                    .unwrap_or_else(PoisonError::into_inner);
+            }
+        }
+
+        if state.closed || state.dropped {
+            return;
+        }
+
+        if state.queue.len() >= self.capacity {
+            match self.policy {
+                SlowConsumerPolicy::DropOldest => {
+                    state.queue.pop_front();
+                    state.stats.dropped += 1;
+                }
+                // The hub is stopping.
+                SlowConsumerPolicy::Block => {
+                    state.stats.dropped += 1;
+                    return;
+                }
+                SlowConsumerPolicy::Disconnect => {
+                    state.stats.dropped += 1;
+                    state.closed = true;
+                    self.changed.notify_all();
+                    return;
+                }
+            }
+        }
+
+        state.queue.push_back(block.clone());
+        state.stats.published += 1;
+        state.stats.lag = state.queue.len();
+        state.stats.max_lag = state.stats.max_lag.max(state.queue.len());
+
+        self.changed.notify_all();
+    }
+
+    fn close(&self) {
+        self.state().closed = true;
+        self.changed.notify_all();
+    }
+
+    fn is_closed(&self) -> bool {
+        let state = self.state();
+        state.closed || state.dropped
+    }
+}
+
+#[derive(Debug)]
+struct HubShared {
+    subscribers: Mutex<Vec<Arc<Subscriber>>>,
+    running: AtomicBool,
+}
+
+/// Fans the blocks of a stream out to any number of subscribers, each
+/// with its own bounded queue, such that several consumers (such as a
+/// logger and a live plot) may share a single reader.
+///
+/// ```rust,ignore
+/// let hub = StreamHub::start(handle)?;
+///
+/// let logger = hub.subscribe(64, SlowConsumerPolicy::Block);
+/// let plot = hub.subscribe(4, SlowConsumerPolicy::DropOldest);
+///
+/// while let Some(block) = plot.recv() {
+///     // ...
+///     println!("plot is {} blocks behind", plot.stats().lag);
+/// }
+/// ```
+///
+/// Blocks are shared between subscribers, rather than copied for each.
+/// Once the hub is stopped (or reading fails), subscribers receive any
+/// blocks left in their queues before `recv` returns `None`.
+#[derive(Debug)]
+pub struct StreamHub {
+    handle: i32,
+    shared: Arc<HubShared>,
+    reader: Option<JoinHandle<Result<(), LJMError>>>,
+}
+
+impl StreamHub {
+    /// A hub without a read loop, to which blocks are given by `publish`,
+    /// such as from a callback registered with `LJMLibrary::set_stream_callback`.
+    pub fn new(handle: i32) -> Self {
+        StreamHub {
+            handle,
+            shared: Arc::new(HubShared {
+                subscribers: Mutex::new(Vec::new()),
+                running: AtomicBool::new(true),
+            }),
+            reader: None,
+        }
+    }
+
+    /// Reads the handle's running stream on a separate thread,
+    /// publishing each block to the hub's subscribers.
+    ///
+    /// Empty blocks (read before enough scans were received) are not published.
+    pub fn start(handle: i32) -> Result<Self, LJMError> {
+        // Fail early, rather than from the read loop.
+        LJMLibrary::stream_shape(handle)?;
+
+        let mut hub = StreamHub::new(handle);
+        let shared = hub.shared.clone();
+
+        hub.reader = Some(std::thread::spawn(move || {
+            let result = loop {
+                if !shared.running.load(Ordering::Relaxed) {
+                    break Ok(());
+                }
+
+                match LJMLibrary::stream_read_detailed(handle) {
+                    Ok(block) if block.scans == 0 => {}
+                    Ok(block) => shared.publish(block),
+                    Err(error) => break Err(error),
+                }
+            };
+
+            shared.running.store(false, Ordering::Relaxed);
+            shared.close();
+            result
+        }));
+
+        Ok(hub)
+    }
+
+    pub fn handle(&self) -> i32 {
+        self.handle
+    }
+
+    /// Adds a subscriber, which receives every block published from now on,
+    /// queueing up to `capacity` blocks before `policy` applies.
+    pub fn subscribe(&self, capacity: usize, policy: SlowConsumerPolicy) -> Subscription {
+        // Checking `running` under the lock ensures the hub either has yet to
+        // close its subscribers, so will close this one, or it is closed here.
+        let mut subscribers = self
+            .shared
+            .subscribers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        let subscriber = Arc::new(Subscriber {
+            capacity: capacity.max(1),
+            policy,
+            state: Mutex::new(SubscriberState {
+                closed: !self.shared.running.load(Ordering::Relaxed),
+                ..SubscriberState::default()
+            }),
+            changed: Condvar::new(),
+        });
+        subscribers.push(subscriber.clone());
+
+        Subscription { subscriber }
+    }
+
+    /// The number of subscribers still connected.
+    pub fn subscribers(&self) -> usize {
+        self.shared.prune().len()
+    }
+
+    /// Queues `block` for every connected subscriber.
+    pub fn publish(&self, block: StreamBlock) {
+        self.shared.publish(block);
+    }
+
+    /// Stops publishing and closes every subscription, returning
+    /// the error which ended the read loop, if any.
+    ///
+    /// The stream itself is left running, to be stopped with `stream_stop`.
+    /// The read loop is not waited on, as a read may not return until the
+    /// stream is stopped (such as for a triggered stream). A read in progress
+    /// is instead left to finish in the background, and its block discarded.
+    pub fn stop(mut self) -> Result<(), LJMError> {
+        self.shutdown()
+    }
+
+    fn shutdown(&mut self) -> Result<(), LJMError> {
+        self.shared.running.store(false, Ordering::Relaxed);
+
+        // Wakes a read loop blocked on a slow subscriber. Taking the lock
+        // ensures it is either waiting, or yet to see the hub has stopped.
+        for subscriber in self.shared.prune() {
+            let _state = subscriber.state();
+            subscriber.changed.notify_all();
+        }
+
+        // Dropping the reader detaches it, and it ends after its current read.
+        let result = match self.reader.take() {
+            Some(reader) if reader.is_finished() => {
+                reader.join().map_err(|_| LJMError::BadState)?
+            }
+            _ => Ok(()),
+        };
+
+        self.shared.close();
+        result
+    }
+}
+
+impl Drop for StreamHub {
+    fn drop(&mut self) {
+        let _ = self.shutdown();
+    }
+}
+
+impl HubShared {
+    // Removes closed subscribers, returning those remaining.
+    fn prune(&self) -> Vec<Arc<Subscriber>> {
+        let mut subscribers = self
+            .subscribers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        subscribers.retain(|subscriber| !subscriber.is_closed());
+        subscribers.clone()
+    }
+
+    fn publish(&self, block: StreamBlock) {
+        let block = Arc::new(block);
+
+        // Not holding the lock, as publishing may block on a subscriber.
+        for subscriber in self.prune() {
+            subscriber.publish(&block, &self.running);
+        }
+    }
+
+    fn close(&self) {
+        for subscriber in self.prune() {
+            subscriber.close();
+        }
+    }
+}
+
+/// A subscriber to a `StreamHub`, which is disconnected when dropped.
+#[derive(Debug)]
+pub struct Subscription {
+    subscriber: Arc<Subscriber>,
+}
+
+impl Subscription {
+    /// Waits for the next block, or returns `None` once
+    /// the subscription is closed and its queue is empty.
+    pub fn recv(&self) -> Option<Arc<StreamBlock>> {
+        let mut state = self.subscriber.state();
+
+        loop {
+            if let Some(block) = Subscription::take(&self.subscriber, &mut state) {
+                return Some(block);
+            }
+            if state.closed {
+                return None;
+            }
+
+            state = self
+                .subscriber
+                .changed
+                .wait(state)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+
+    /// Waits up to `timeout` for the next block.
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<Arc<StreamBlock>> {
+        let deadline = Instant::now() + timeout;
+        let mut state = self.subscriber.state();
+
+        loop {
+            if let Some(block) = Subscription::take(&self.subscriber, &mut state) {
+                return Some(block);
+            }
+
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if state.closed || remaining.is_zero() {
+                return None;
+            }
+
+            state = self
+                .subscriber
+                .changed
+                .wait_timeout(state, remaining)
+                .unwrap_or_else(PoisonError::into_inner)
+                .0;
+        }
+    }
+
+    /// Takes the next block if one is queued, without waiting.
+    pub fn try_recv(&self) -> Option<Arc<StreamBlock>> {
+        Subscription::take(&self.subscriber, &mut self.subscriber.state())
+    }
+
+    /// Whether blocks are still being published to the subscription.
+    pub fn is_connected(&self) -> bool {
+        !self.subscriber.state().closed
+    }
+
+    pub fn stats(&self) -> SubscriberStats {
+        self.subscriber.state().stats
+    }
+
+    fn take(subscriber: &Subscriber, state: &mut SubscriberState) -> Option<Arc<StreamBlock>> {
+        let block = state.queue.pop_front()?;
+        state.stats.received += 1;
+        state.stats.lag = state.queue.len();
+
+        // Makes room for a hub waiting on this subscriber.
+        subscriber.changed.notify_all();
+        Some(block)
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        self.subscriber.state().dropped = true;
+        self.subscriber.changed.notify_all();
+    }
+}
@@ -1,12 +1,56 @@
 #![doc = include_str!("../../docs/ljm.md")]
 
+#[cfg(feature = "stream")]
+pub use calibration::*;
 pub use core::*;
 pub use error::*;
 pub use handle::*;
+#[cfg(feature = "stream")]
+pub use hub::*;
 pub use lua::*;
+#[cfg(feature = "stream")]
+pub use record::*;
+#[cfg(feature = "stream")]
+pub use replay::*;
+#[cfg(feature = "stream")]
+pub use session::*;
+#[cfg(feature = "stream")]
+pub use stream::*;
+#[cfg(all(feature = "stream", feature = "tokio"))]
+pub use stream_async::*;
+#[cfg(feature = "stream")]
+pub use stream_config::*;
+#[cfg(feature = "stream")]
+pub use stream_out::*;
+#[cfg(feature = "stream")]
+pub use sync::*;
+pub use transaction::*;
+#[cfg(feature = "stream")]
+pub use waveform::*;
 
+#[cfg(feature = "stream")]
+pub mod calibration;
 pub mod core;
 pub mod error;
 pub mod handle;
+#[cfg(feature = "stream")]
+pub mod hub;
 pub mod lua;
+#[cfg(feature = "stream")]
+pub mod record;
+#[cfg(feature = "stream")]
+pub mod replay;
+#[cfg(feature = "stream")]
+pub mod session;
 pub mod stream;
+#[cfg(all(feature = "stream", feature = "tokio"))]
+pub mod stream_async;
+#[cfg(feature = "stream")]
+pub mod stream_config;
+#[cfg(feature = "stream")]
+pub mod stream_out;
+#[cfg(feature = "stream")]
+pub mod sync;
+pub mod transaction;
+#[cfg(feature = "stream")]
+pub mod waveform;
@@ -0,0 +1,489 @@
+//! Recording of stream data to disk, and loading it back for offline analysis.
+//!
+//! Recordings are written in one of two formats.
+//!
+//! ## CSV
+//!
+//! Metadata lines prefixed with `#`, followed by a header of `time` and
+//! the channel names, then one row per scan. `time` is the seconds since
+//! the stream started. Names containing commas, quotes or line breaks are
+//! quoted, with quotes doubled.
+//!
+//! ```text
+//! # serial_number=470012345
+//! # scan_rate=1000
+//! # started_at_unix_ns=1700000000000000000
+//! # addresses=0,2
+//! time,AIN0,AIN1
+//! 0,0.0012,1.2506
+//! 0.001,0.0014,1.2503
+//! ```
+//!
+//! ## Binary
+//!
+//! A header followed by the interleaved scans, all little-endian.
+//!
+//! | Field          | Type                  |                                   |
+//! |----------------|-----------------------|-----------------------------------|
+//! | magic          | `[u8; 4]`             | `LJMR`                            |
+//! | version        | `u16`                 | `1`                               |
+//! | serial_number  | `i32`                 |                                   |
+//! | scan_rate      | `f64`                 | Actual scans per second           |
+//! | started_at     | `i64`                 | Nanoseconds since the UNIX epoch  |
+//! | num_channels   | `u32`                 |                                   |
+//! | channels       | per channel:          |                                   |
+//! |                | `i32` address         |                                   |
+//! |                | `u16` name length     |                                   |
+//! |                | `[u8]` name           | UTF-8                             |
+//! | data           | `f64` until EOF       | `num_channels` values per scan    |
+
+use std::borrow::Cow;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use crate::ljm::stream::deinterleave;
+use crate::{LJMError, LJMLibrary, StreamBlock};
+
+const BINARY_MAGIC: &[u8; 4] = b"LJMR";
+const BINARY_VERSION: u16 = 1;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RecordingFormat {
+    Csv,
+    Binary,
+}
+
+/// Describes the stream a recording was taken from.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecordingHeader {
+    pub serial_number: i32,
+    pub scan_rate: f64,
+    pub started_at: SystemTime,
+    pub names: Vec<String>,
+    pub addresses: Vec<i32>,
+}
+
+impl RecordingHeader {
+    /// Describes the stream running on `handle`.
+    pub fn from_stream(handle: i32) -> Result<Self, LJMError> {
+        let info = LJMLibrary::get_handle_info(handle)?;
+        let (names, addresses) = LJMLibrary::stream_channels(handle)?;
+        let clock = LJMLibrary::stream_clock(handle)?;
+
+        Ok(RecordingHeader {
+            serial_number: info.serial_number,
+            scan_rate: clock.scan_rate(),
+            started_at: clock.started_at(),
+            names,
+            addresses,
+        })
+    }
+
+    pub fn num_channels(&self) -> usize {
+        self.addresses.len()
+    }
+
+    fn started_at_ns(&self) -> i64 {
+        match self.started_at.duration_since(SystemTime::UNIX_EPOCH) {
+            Ok(since) => since.as_nanos() as i64,
+            Err(before) => -(before.duration().as_nanos() as i64),
+        }
+    }
+
+    fn from_started_at_ns(ns: i64) -> SystemTime {
+        match ns {
+            0.. => SystemTime::UNIX_EPOCH + Duration::from_nanos(ns as u64),
+            _ => SystemTime::UNIX_EPOCH - Duration::from_nanos(ns.unsigned_abs()),
+        }
+    }
+}
+
+/// Writes stream blocks to a recording, as they are read.
+///
+/// ```rust,ignore
+/// let mut recorder = StreamRecorder::create("run.ljmr", RecordingFormat::Binary, handle)?;
+///
+/// for _ in 0..100 {
+///     recorder.record(handle)?;
+/// }
+///
+/// recorder.finish()?;
+/// ```
+#[derive(Debug)]
+pub struct StreamRecorder<W: Write> {
+    writer: W,
+    format: RecordingFormat,
+    header: RecordingHeader,
+    scans: u64,
+}
+
+impl StreamRecorder<BufWriter<File>> {
+    /// Creates a recording at `path` of the stream running on `handle`.
+    pub fn create<P: AsRef<Path>>(
+        path: P,
+        format: RecordingFormat,
+        handle: i32,
+    ) -> Result<Self, LJMError> {
+        let header = RecordingHeader::from_stream(handle)?;
+        let writer = BufWriter::new(File::create(path)?);
+
+        StreamRecorder::new(writer, format, header)
+    }
+}
+
+impl<W: Write> StreamRecorder<W> {
+    /// Writes the header of the recording to `writer`.
+    pub fn new(
+        mut writer: W,
+        format: RecordingFormat,
+        header: RecordingHeader,
+    ) -> Result<Self, LJMError> {
+        match format {
+            RecordingFormat::Csv => write_csv_header(&mut writer, &header)?,
+            RecordingFormat::Binary => write_binary_header(&mut writer, &header)?,
+        }
+
+        Ok(StreamRecorder {
+            writer,
+            format,
+            header,
+            scans: 0,
+        })
+    }
+
+    pub fn header(&self) -> &RecordingHeader {
+        &self.header
+    }
+
+    /// The number of scans written so far.
+    pub fn scans(&self) -> u64 {
+        self.scans
+    }
+
+    /// Reads a block from the stream on `handle` and writes it, returning the block.
+    pub fn record(&mut self, handle: i32) -> Result<StreamBlock, LJMError> {
+        let block = LJMLibrary::stream_read_detailed(handle)?;
+        self.write_block(&block)?;
+
+        Ok(block)
+    }
+
+    pub fn write_block(&mut self, block: &StreamBlock) -> Result<(), LJMError> {
+        let num_channels = self.header.num_channels();
+        if block.data.len().checked_rem(num_channels) != Some(0) {
+            return Err(LJMError::BufferSizeMismatch(
+                block.scans * num_channels,
+                block.data.len(),
+            ));
+        }
+
+        match self.format {
+            RecordingFormat::Csv => {
+                for (i, scan) in block.data.chunks_exact(num_channels).enumerate() {
+                    let elapsed = match &block.timing {
+                        Some(timing) => timing.elapsed(i).as_secs_f64(),
+                        None => (self.scans + i as u64) as f64 / self.header.scan_rate,
+                    };
+
+                    write!(self.writer, "{}", elapsed)?;
+                    for value in scan {
+                        write!(self.writer, ",{}", value)?;
+                    }
+                    writeln!(self.writer)?;
+                }
+            }
+            RecordingFormat::Binary => {
+                for value in &block.data {
+                    self.writer.write_all(&value.to_le_bytes())?;
+                }
+            }
+        }
+
+        self.scans += (block.data.len() / num_channels) as u64;
+        Ok(())
+    }
+
+    /// Flushes the recording, returning the underlying writer.
+    pub fn finish(mut self) -> Result<W, LJMError> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+fn write_csv_header<W: Write>(writer: &mut W, header: &RecordingHeader) -> Result<(), LJMError> {
+    let addresses: Vec<String> = header.addresses.iter().map(|a| a.to_string()).collect();
+
+    writeln!(writer, "# serial_number={}", header.serial_number)?;
+    writeln!(writer, "# scan_rate={}", header.scan_rate)?;
+    writeln!(writer, "# started_at_unix_ns={}", header.started_at_ns())?;
+    writeln!(writer, "# addresses={}", addresses.join(","))?;
+
+    write!(writer, "time")?;
+    for name in &header.names {
+        write!(writer, ",{}", csv_field(name))?;
+    }
+    writeln!(writer)?;
+
+    Ok(())
+}
+
+fn write_binary_header<W: Write>(writer: &mut W, header: &RecordingHeader) -> Result<(), LJMError> {
+    writer.write_all(BINARY_MAGIC)?;
+    writer.write_all(&BINARY_VERSION.to_le_bytes())?;
+    writer.write_all(&header.serial_number.to_le_bytes())?;
+    writer.write_all(&header.scan_rate.to_le_bytes())?;
+    writer.write_all(&header.started_at_ns().to_le_bytes())?;
+    writer.write_all(&(header.num_channels() as u32).to_le_bytes())?;
+
+    for (address, name) in header.addresses.iter().zip(&header.names) {
+        let name = name.as_bytes();
+        let length = u16::try_from(name.len())
+            .map_err(|_| LJMError::InvalidRecording("channel name too long".to_string()))?;
+
+        writer.write_all(&address.to_le_bytes())?;
+        writer.write_all(&length.to_le_bytes())?;
+        writer.write_all(name)?;
+    }
+
+    Ok(())
+}
+
+/// A recording loaded back into per-channel vectors.
+#[derive(Clone, Debug)]
+pub struct Recording {
+    pub header: RecordingHeader,
+    /// The values of each channel, in scan list order.
+    pub channels: Vec<Vec<f64>>,
+}
+
+impl Recording {
+    /// Loads a recording in either format, detected from its contents.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, LJMError> {
+        let mut reader = BufReader::new(File::open(path)?);
+
+        if reader.fill_buf()?.starts_with(BINARY_MAGIC) {
+            Recording::read_binary(reader)
+        } else {
+            Recording::read_csv(reader)
+        }
+    }
+
+    pub fn read_csv<R: BufRead>(reader: R) -> Result<Self, LJMError> {
+        let mut serial_number = None;
+        let mut scan_rate = None;
+        let mut started_at = None;
+        let mut addresses = None;
+        let mut names: Option<Vec<String>> = None;
+        let mut channels: Vec<Vec<f64>> = Vec::new();
+
+        // The column header so far, if a quoted name spans several lines
+        let mut header = String::new();
+
+        for line in reader.lines() {
+            let line = line?;
+
+            if !header.is_empty() {
+                header.push('\n');
+                header.push_str(&line);
+            } else if let Some(metadata) = line.strip_prefix('#') {
+                let (key, value) = metadata
+                    .trim()
+                    .split_once('=')
+                    .ok_or_else(|| invalid(format!("bad metadata line {:?}", line)))?;
+
+                match key {
+                    "serial_number" => serial_number = Some(parse(value)?),
+                    "scan_rate" => scan_rate = Some(parse(value)?),
+                    "started_at_unix_ns" => started_at = Some(parse(value)?),
+                    "addresses" => {
+                        addresses = Some(
+                            value
+                                .split(',')
+                                .filter(|a| !a.is_empty())
+                                .map(parse)
+                                .collect::<Result<Vec<i32>, LJMError>>()?,
+                        )
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+
+            match &names {
+                None => {
+                    if header.is_empty() {
+                        header = line;
+                    }
+
+                    // `None` until the end of a quoted name spanning several lines
+                    if let Some(columns) = split_csv_fields(&header) {
+                        channels = vec![Vec::new(); columns.len().saturating_sub(1)];
+                        names = Some(columns.into_iter().skip(1).collect());
+                        header.clear();
+                    }
+                }
+                Some(_) if line.is_empty() => {}
+                Some(_) => {
+                    let values: Vec<f64> = line
+                        .split(',')
+                        .skip(1)
+                        .map(parse)
+                        .collect::<Result<_, _>>()?;
+
+                    if values.len() != channels.len() {
+                        return Err(invalid(format!("bad row {:?}", line)));
+                    }
+
+                    for (channel, value) in channels.iter_mut().zip(values) {
+                        channel.push(value);
+                    }
+                }
+            }
+        }
+
+        if !header.is_empty() {
+            return Err(invalid("unterminated quoted name in column header"));
+        }
+
+        let names = names.ok_or_else(|| invalid("missing column header".to_string()))?;
+        let addresses = addresses.ok_or_else(|| invalid("missing addresses".to_string()))?;
+        if addresses.len() != names.len() {
+            return Err(invalid("addresses do not match columns".to_string()));
+        }
+
+        Ok(Recording {
+            header: RecordingHeader {
+                serial_number: serial_number.ok_or_else(|| invalid("missing serial_number"))?,
+                scan_rate: scan_rate.ok_or_else(|| invalid("missing scan_rate"))?,
+                started_at: RecordingHeader::from_started_at_ns(
+                    started_at.ok_or_else(|| invalid("missing started_at_unix_ns"))?,
+                ),
+                names,
+                addresses,
+            },
+            channels,
+        })
+    }
+
+    pub fn read_binary<R: Read>(mut reader: R) -> Result<Self, LJMError> {
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != BINARY_MAGIC {
+            return Err(invalid("not a binary recording"));
+        }
+
+        let version = u16::from_le_bytes(read_array(&mut reader)?);
+        if version != BINARY_VERSION {
+            return Err(invalid(format!("unsupported version {}", version)));
+        }
+
+        let serial_number = i32::from_le_bytes(read_array(&mut reader)?);
+        let scan_rate = f64::from_le_bytes(read_array(&mut reader)?);
+        let started_at = i64::from_le_bytes(read_array(&mut reader)?);
+        let num_channels = u32::from_le_bytes(read_array(&mut reader)?) as usize;
+
+        // Grown as channels are read, as `num_channels` is not to be trusted.
+        let mut names = Vec::new();
+        let mut addresses = Vec::new();
+        for _ in 0..num_channels {
+            addresses.push(i32::from_le_bytes(read_array(&mut reader)?));
+
+            let length = u16::from_le_bytes(read_array(&mut reader)?) as usize;
+            let mut name = vec![0; length];
+            reader.read_exact(&mut name)?;
+            names.push(String::from_utf8(name).map_err(|e| invalid(e.to_string()))?);
+        }
+
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        let value_size = std::mem::size_of::<f64>();
+        if data.len().checked_rem(value_size * num_channels) != Some(0) {
+            return Err(invalid("truncated scan data"));
+        }
+
+        let values: Vec<f64> = data
+            .chunks_exact(value_size)
+            .map(|bytes| f64::from_le_bytes(bytes.try_into().expect("chunk of 8 bytes")))
+            .collect();
+
+        Ok(Recording {
+            header: RecordingHeader {
+                serial_number,
+                scan_rate,
+                started_at: RecordingHeader::from_started_at_ns(started_at),
+                names,
+                addresses,
+            },
+            channels: deinterleave(&values, num_channels),
+        })
+    }
+
+    pub fn num_scans(&self) -> usize {
+        self.channels.first().map_or(0, Vec::len)
+    }
+
+    /// The values of the channel recorded under `name`.
+    pub fn channel(&self, name: &str) -> Option<&[f64]> {
+        let index = self.header.names.iter().position(|n| n == name)?;
+        Some(&self.channels[index])
+    }
+
+    /// The values of the channel recorded from `address`.
+    pub fn channel_by_address(&self, address: i32) -> Option<&[f64]> {
+        let index = self.header.addresses.iter().position(|a| *a == address)?;
+        Some(&self.channels[index])
+    }
+}
+
+fn invalid<T: ToString>(reason: T) -> LJMError {
+    LJMError::InvalidRecording(reason.to_string())
+}
+
+fn parse<T: std::str::FromStr>(value: &str) -> Result<T, LJMError> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| invalid(format!("bad value {:?}", value)))
+}
+
+// Quotes a CSV field if it contains a comma, quote or line break.
+fn csv_field(field: &str) -> Cow<'_, str> {
+    if field.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
+
+// Splits a line of CSV fields, unquoting any quoted fields,
+// or returns `None` if the last quoted field is unterminated.
+fn split_csv_fields(line: &str) -> Option<Vec<String>> {
+    let mut fields = vec![String::new()];
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let field = fields.last_mut().expect("at least one field");
+
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' if quoted || field.is_empty() => quoted = !quoted,
+            ',' if !quoted => fields.push(String::new()),
+            c => field.push(c),
+        }
+    }
+
+    (!quoted).then_some(fields)
+}
+
+fn read_array<R: Read, const N: usize>(reader: &mut R) -> Result<[u8; N], LJMError> {
+    let mut bytes = [0; N];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
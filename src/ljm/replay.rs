@@ -0,0 +1,138 @@
+use std::time::{Duration, Instant};
+
+use crate::{LJMError, Recording};
+
+/// Serves a `Recording` as if it were being streamed from a device,
+/// for exercising stream processing without hardware.
+///
+/// A replay is opened as a virtual handle with `LJMLibrary::open_replay`,
+/// which is then used with `stream_start`, `stream_read` and `stream_stop`
+/// as a real handle would be. The scan list must be a subset of the
+/// recorded channels.
+///
+/// ```rust,ignore
+/// let replay = Replay::new(Recording::open("run.ljmr")?).with_speed(10.0);
+/// let handle = LJMLibrary::open_replay(replay)?;
+///
+/// LJMLibrary::stream_start_addr(handle, 100, 1000.0, vec!["AIN0"])?;
+/// let values = LJMLibrary::stream_read(handle)?;
+/// ```
+#[derive(Debug)]
+pub struct Replay {
+    recording: Recording,
+    speed: f64,
+    looping: bool,
+
+    // The recording channel of each address in the scan list
+    channels: Vec<usize>,
+    position: usize,
+    started: Option<Instant>,
+}
+
+impl Replay {
+    pub fn new(recording: Recording) -> Self {
+        Replay {
+            recording,
+            speed: 1.0,
+            looping: false,
+            channels: Vec::new(),
+            position: 0,
+            started: None,
+        }
+    }
+
+    /// Replays `speed` times faster than recorded. An infinite
+    /// speed serves data as fast as it is read.
+    pub fn with_speed(mut self, speed: f64) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Restarts from the beginning of the recording once it ends,
+    /// rather than returning `LJMError::EndOfRecording`.
+    pub fn with_looping(mut self, looping: bool) -> Self {
+        self.looping = looping;
+        self
+    }
+
+    pub fn recording(&self) -> &Recording {
+        &self.recording
+    }
+
+    /// The number of scans served since the replay started.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Starts serving the recorded channels of `scan_list` from the beginning,
+    /// returning the recorded scan rate.
+    pub fn start(&mut self, scan_list: &[i32]) -> Result<f64, LJMError> {
+        let addresses = &self.recording.header.addresses;
+
+        self.channels = scan_list
+            .iter()
+            .map(|address| {
+                addresses.iter().position(|a| a == address).ok_or_else(|| {
+                    LJMError::InvalidRecording(format!("address {} was not recorded", address))
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        self.position = 0;
+        self.started = Some(Instant::now());
+
+        Ok(self.recording.header.scan_rate)
+    }
+
+    pub fn stop(&mut self) {
+        self.started = None;
+    }
+
+    /// Fills `buffer` with the next scans, waiting until they would have been
+    /// streamed. Returns synthetic `(device, LJM)` backlogs, where the LJM
+    /// backlog is the scans which were due but not yet read.
+    pub fn read(&mut self, buffer: &mut [f64]) -> Result<(i32, i32), LJMError> {
+        let started = self.started.ok_or(LJMError::StreamNotStarted)?;
+        let num_channels = self.channels.len();
+        let num_scans = buffer.len().checked_div(num_channels).unwrap_or(0);
+        let recorded_scans = self.recording.num_scans();
+
+        if num_scans == 0 {
+            return Ok((0, 0));
+        }
+
+        if !self.looping && self.position + num_scans > recorded_scans {
+            return Err(LJMError::EndOfRecording);
+        }
+        if recorded_scans == 0 {
+            return Err(LJMError::EndOfRecording);
+        }
+
+        let scans_per_second = self.recording.header.scan_rate * self.speed;
+        let due = (self.position + num_scans) as f64 / scans_per_second;
+        if due.is_finite() {
+            let elapsed = started.elapsed().as_secs_f64();
+            if due > elapsed {
+                std::thread::sleep(Duration::from_secs_f64(due - elapsed));
+            }
+        }
+
+        for (i, scan) in buffer.chunks_exact_mut(num_channels).enumerate() {
+            let source = (self.position + i) % recorded_scans;
+
+            for (value, channel) in scan.iter_mut().zip(&self.channels) {
+                *value = self.recording.channels[*channel][source];
+            }
+        }
+        self.position += num_scans;
+
+        let available = started.elapsed().as_secs_f64() * scans_per_second;
+        let backlog = if available.is_finite() {
+            (available as usize).saturating_sub(self.position)
+        } else {
+            0
+        };
+
+        Ok((0, backlog.min(i32::MAX as usize) as i32))
+    }
+}
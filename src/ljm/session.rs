@@ -0,0 +1,108 @@
+use std::fmt::Display;
+
+use crate::{LJMError, LJMLibrary, LJMStream, StreamBlock, StreamBuffer, StreamFrame};
+
+/// A running stream which is stopped when dropped, tying
+/// the lifetime of the stream to the enclosing scope.
+///
+/// If the owning code panics mid-acquisition, the stream is stopped
+/// during unwinding rather than left running on the device.
+///
+/// ```rust,ignore
+/// let session = StreamSession::start_addr(handle, scans_per_read, 1000.0, vec!["AIN0"])?;
+///
+/// for _ in 0..100 {
+///     let values = session.read()?;
+/// }
+/// // Stream stopped here
+/// ```
+#[derive(Debug)]
+pub struct StreamSession {
+    handle: i32,
+    scan_rate: f64,
+    stopped: bool,
+}
+
+impl StreamSession {
+    /// Starts a stream as with `LJMLibrary::stream_start`.
+    pub fn start(
+        handle: i32,
+        scans_per_read: i32,
+        suggested_scan_rate: f64,
+        addresses: Vec<i32>,
+    ) -> Result<Self, LJMError> {
+        let scan_rate =
+            LJMLibrary::stream_start(handle, scans_per_read, suggested_scan_rate, addresses)?;
+
+        Ok(StreamSession::new(handle, scan_rate))
+    }
+
+    /// Starts a stream as with `LJMLibrary::stream_start_addr`.
+    pub fn start_addr<T>(
+        handle: i32,
+        scans_per_read: i32,
+        suggested_scan_rate: f64,
+        streams: Vec<T>,
+    ) -> Result<Self, LJMError>
+    where
+        T: ToString + Display,
+    {
+        let scan_rate =
+            LJMLibrary::stream_start_addr(handle, scans_per_read, suggested_scan_rate, streams)?;
+
+        Ok(StreamSession::new(handle, scan_rate))
+    }
+
+    fn new(handle: i32, scan_rate: f64) -> Self {
+        StreamSession {
+            handle,
+            scan_rate,
+            stopped: false,
+        }
+    }
+
+    pub fn handle(&self) -> i32 {
+        self.handle
+    }
+
+    /// The actual scan rate chosen by the device.
+    pub fn scan_rate(&self) -> f64 {
+        self.scan_rate
+    }
+
+    pub fn read(&self) -> Result<Vec<f64>, LJMError> {
+        LJMLibrary::stream_read(self.handle)
+    }
+
+    pub fn read_detailed(&self) -> Result<StreamBlock, LJMError> {
+        LJMLibrary::stream_read_detailed(self.handle)
+    }
+
+    pub fn read_frame(&self) -> Result<StreamFrame, LJMError> {
+        LJMLibrary::stream_read_frame(self.handle)
+    }
+
+    pub fn read_into(&self, buffer: &mut [f64]) -> Result<(i32, i32), LJMError> {
+        LJMLibrary::stream_read_into(self.handle, buffer)
+    }
+
+    /// Creates a `StreamBuffer` sized to this stream.
+    pub fn buffer(&self) -> Result<StreamBuffer, LJMError> {
+        StreamBuffer::new(self.handle)
+    }
+
+    /// Stops the stream, returning any error from LJM
+    /// which would otherwise be discarded on drop.
+    pub fn stop(mut self) -> Result<Option<LJMStream>, LJMError> {
+        self.stopped = true;
+        LJMLibrary::stream_stop(self.handle)
+    }
+}
+
+impl Drop for StreamSession {
+    fn drop(&mut self) {
+        if !self.stopped {
+            let _ = LJMLibrary::stream_stop(self.handle);
+        }
+    }
+}
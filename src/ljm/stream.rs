@@ -1,3 +1,19 @@
+#[cfg(feature = "stream")]
+use crate::{CalibrationTable, LJMError, LJMLibrary, StreamConfig};
+#[cfg(feature = "stream")]
+use std::collections::HashMap;
+#[cfg(feature = "stream")]
+use std::iter::{Skip, StepBy};
+#[cfg(feature = "stream")]
+use std::slice::{ChunksExact, Iter};
+#[cfg(feature = "stream")]
+use std::sync::{
+    atomic::{AtomicBool, AtomicI32, AtomicU64, Ordering},
+    Arc, Mutex, PoisonError,
+};
+#[cfg(feature = "stream")]
+use std::time::{Duration, Instant, SystemTime};
+
 #[derive(Clone)]
 #[cfg(feature = "stream")]
 pub struct LJMStream {
@@ -6,4 +22,829 @@ pub struct LJMStream {
 
     // Stores a list of the internal LJM addresses
     pub(crate) scan_list: Vec<i32>,
+
+    // Where the scan list differs from that streamed by the device,
+    // with the device's data read into `capture_buffer` to be combined
+    pub(crate) layout: CaptureLayout,
+    pub(crate) capture_buffer: Arc<Mutex<Vec<f64>>>,
+
+    // The register names given for each address in `scan_list`
+    pub(crate) names: Vec<String>,
+
+    // The callback registered with `LJM_SetStreamCallback`, if any.
+    // LJM holds a pointer to it, so it must live until the callback is cleared.
+    pub(crate) callback: Option<Arc<StreamCallback>>,
+
+    // Callbacks replaced by `callback`, which LJM's stream thread may still
+    // be running, so are kept until the stream is stopped.
+    pub(crate) retired_callbacks: Vec<Arc<StreamCallback>>,
+
+    // The backlogs reported by the most recent read
+    pub(crate) backlog: Arc<StreamBacklog>,
+
+    // The actual scan rate, start time and count of scans read
+    pub(crate) clock: Arc<StreamClock>,
+
+    // The configuration applied by `stream_start_config`, if used
+    pub(crate) config: Option<StreamConfig>,
+
+    // For binary streams, the calibration read when the stream was started
+    pub(crate) calibration: Option<CalibrationTable>,
+
+    // For triggered streams, whether any scans have been received
+    pub(crate) triggered: Option<Arc<AtomicBool>>,
+
+    // The previous values of library configs set by `stream_start_config`
+    // for the life of the stream, restored when it is stopped
+    pub(crate) restore_configs: Vec<(&'static str, f64)>,
+}
+
+/// Placed in stream data by LJM in place of values lost to a device buffer overflow
+/// (when `LJM_STREAM_AUTO_RECOVERY` is enabled).
+#[cfg(feature = "stream")]
+pub const LJM_DUMMY_VALUE: f64 = -9999.0;
+/// Placed in stream data by LJM for scans which were not read.
+#[cfg(feature = "stream")]
+pub const LJM_SCAN_NOT_READ: f64 = -8888.0;
+
+/// A single read of stream data, interleaved as
+/// `stream[0], ..., stream[N], stream[0], ...`.
+#[derive(Clone, Debug)]
+#[cfg(feature = "stream")]
+pub struct StreamBlock {
+    pub data: Vec<f64>,
+    pub scans: usize,
+
+    // Scans left in the device buffer when data was last collected
+    pub dev_scan_backlog: i32,
+    // Scans left in the LJM buffer after this read
+    pub ljm_scan_backlog: i32,
+
+    /// Whether `data` contains `LJM_DUMMY_VALUE`, indicating lost scans.
+    pub has_dummy_values: bool,
+    /// Whether `data` contains `LJM_SCAN_NOT_READ`, indicating skipped scans.
+    pub has_skipped_scans: bool,
+
+    /// When each scan in the block was taken.
+    pub timing: Option<StreamTiming>,
+
+    /// Whether the stream's trigger has fired (always set for untriggered streams).
+    /// Blocks read before it has fired are empty.
+    pub triggered: bool,
+}
+
+#[cfg(feature = "stream")]
+impl StreamBlock {
+    /// A block of `data`, interleaved over `num_channels` channels,
+    /// flagging any `LJM_DUMMY_VALUE` or `LJM_SCAN_NOT_READ` values.
+    pub fn new(
+        data: Vec<f64>,
+        num_channels: usize,
+        dev_scan_backlog: i32,
+        ljm_scan_backlog: i32,
+        timing: Option<StreamTiming>,
+    ) -> Self {
+        StreamBlock {
+            scans: data.len().checked_div(num_channels).unwrap_or(0),
+            has_dummy_values: data.contains(&LJM_DUMMY_VALUE),
+            has_skipped_scans: data.contains(&LJM_SCAN_NOT_READ),
+            data,
+            dev_scan_backlog,
+            ljm_scan_backlog,
+            timing,
+            triggered: true,
+        }
+    }
+
+    // An empty block, for a read made before enough scans were received.
+    pub(crate) fn empty(triggered: bool) -> Self {
+        StreamBlock {
+            data: Vec::new(),
+            scans: 0,
+            dev_scan_backlog: 0,
+            ljm_scan_backlog: 0,
+            has_dummy_values: false,
+            has_skipped_scans: false,
+            timing: None,
+            triggered,
+        }
+    }
+
+    /// The time at which each scan in the block was taken,
+    /// or empty if the block has no timing.
+    pub fn timestamps(&self) -> Vec<SystemTime> {
+        match &self.timing {
+            Some(timing) => (0..self.scans).map(|scan| timing.timestamp(scan)).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Whether the stream is falling behind, with scans lost or backlogs remaining.
+    pub fn is_behind(&self) -> bool {
+        self.has_dummy_values
+            || self.has_skipped_scans
+            || self.dev_scan_backlog > 0
+            || self.ljm_scan_backlog > 0
+    }
+}
+
+/// A `StreamBlock` alongside the channels of the stream, from
+/// `LJMLibrary::stream_read_frame`, such that the interleaved data
+/// may be viewed per-channel (by name or address) or per-scan.
+///
+/// Channel views borrow from the block, so no data is copied.
+#[derive(Clone, Debug)]
+#[cfg(feature = "stream")]
+pub struct StreamFrame {
+    pub block: StreamBlock,
+    names: Vec<String>,
+    addresses: Vec<i32>,
+}
+
+#[cfg(feature = "stream")]
+impl StreamFrame {
+    pub fn new(block: StreamBlock, names: Vec<String>, addresses: Vec<i32>) -> Self {
+        StreamFrame {
+            block,
+            names,
+            addresses,
+        }
+    }
+
+    /// The register names of each channel, in scan order.
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// The addresses of each channel, in scan order.
+    pub fn addresses(&self) -> &[i32] {
+        &self.addresses
+    }
+
+    pub fn num_channels(&self) -> usize {
+        self.addresses.len()
+    }
+
+    pub fn num_scans(&self) -> usize {
+        self.block.scans
+    }
+
+    /// The channel streamed under `name`, as given to `stream_start_addr`.
+    pub fn channel(&self, name: &str) -> Option<Channel<'_>> {
+        let index = self.names.iter().position(|n| n == name)?;
+        Some(self.channel_at(index))
+    }
+
+    /// The channel streamed from `address`.
+    pub fn channel_by_address(&self, address: i32) -> Option<Channel<'_>> {
+        let index = self.addresses.iter().position(|a| *a == address)?;
+        Some(self.channel_at(index))
+    }
+
+    /// Iterates over every channel, in scan order.
+    pub fn channels(&self) -> impl Iterator<Item = Channel<'_>> {
+        (0..self.num_channels()).map(|index| self.channel_at(index))
+    }
+
+    /// Iterates over each scan, with one value per channel in scan order.
+    pub fn scans(&self) -> ChunksExact<'_, f64> {
+        self.block.data.chunks_exact(self.num_channels().max(1))
+    }
+
+    /// Copies each channel into a map keyed by channel name.
+    pub fn to_map(&self) -> HashMap<String, Vec<f64>> {
+        self.channels()
+            .map(|channel| (channel.name.to_string(), channel.to_vec()))
+            .collect()
+    }
+
+    fn channel_at(&self, index: usize) -> Channel<'_> {
+        Channel {
+            name: &self.names[index],
+            address: self.addresses[index],
+            index,
+            stride: self.num_channels(),
+            data: &self.block.data,
+        }
+    }
+}
+
+/// A borrowed view of a single channel of a `StreamFrame`.
+#[derive(Clone, Copy, Debug)]
+#[cfg(feature = "stream")]
+pub struct Channel<'a> {
+    pub name: &'a str,
+    pub address: i32,
+    index: usize,
+    stride: usize,
+    data: &'a [f64],
+}
+
+#[cfg(feature = "stream")]
+impl<'a> Channel<'a> {
+    /// Iterates over this channel's value in each scan.
+    pub fn iter(&self) -> StepBy<Skip<Iter<'a, f64>>> {
+        self.data.iter().skip(self.index).step_by(self.stride)
+    }
+
+    /// The value of this channel in scan `scan`.
+    pub fn get(&self, scan: usize) -> Option<f64> {
+        self.data.get(scan * self.stride + self.index).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len() / self.stride
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn to_vec(&self) -> Vec<f64> {
+        self.iter().copied().collect()
+    }
+}
+
+#[cfg(feature = "stream")]
+impl<'a> IntoIterator for Channel<'a> {
+    type Item = &'a f64;
+    type IntoIter = StepBy<Skip<Iter<'a, f64>>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// The address of `CORE_TIMER`, which counts at 40 MHz.
+#[cfg(feature = "stream")]
+pub const CORE_TIMER_ADDRESS: i32 = 61520;
+#[cfg(feature = "stream")]
+const CORE_TIMER_FREQUENCY: f64 = 40_000_000.0;
+
+/// The address of `SYSTEM_TIMER_20HZ`, which counts at 20 Hz.
+#[cfg(feature = "stream")]
+pub const SYSTEM_TIMER_20HZ_ADDRESS: i32 = 61522;
+#[cfg(feature = "stream")]
+const SYSTEM_TIMER_20HZ_FREQUENCY: f64 = 20.0;
+
+// Streamed 32-bit timers only report their lower 16 bits,
+// unless their upper 16 bits are captured alongside them.
+#[cfg(feature = "stream")]
+const TIMER_WRAP: i64 = 1 << 16;
+#[cfg(feature = "stream")]
+const CAPTURED_TIMER_WRAP: i64 = 1 << 32;
+
+/// When the scans of a `StreamBlock` were taken.
+///
+/// Scan times are nominal (`scan / scan_rate` after the stream started),
+/// unless `CORE_TIMER` is in the scan list, in which case they are measured
+/// by the device's timer. `SYSTEM_TIMER_20HZ` ticks too slowly to time each
+/// scan, so if streamed instead, it only corrects the drift of nominal times.
+#[derive(Clone, Debug)]
+#[cfg(feature = "stream")]
+pub struct StreamTiming {
+    /// The actual scan rate chosen by the device.
+    pub scan_rate: f64,
+    pub started_at: SystemTime,
+    pub started: Instant,
+    /// The number of scans in the stream before this block.
+    pub first_scan: u64,
+    /// The time since the stream started of each scan,
+    /// as measured or corrected by a timer in the scan list.
+    pub measured: Option<Vec<Duration>>,
+}
+
+#[cfg(feature = "stream")]
+impl StreamTiming {
+    /// The time since the stream started at which `scan` (within the block) was taken.
+    pub fn elapsed(&self, scan: usize) -> Duration {
+        self.measured
+            .as_ref()
+            .and_then(|measured| measured.get(scan).copied())
+            .unwrap_or_else(|| {
+                Duration::from_secs_f64((self.first_scan + scan as u64) as f64 / self.scan_rate)
+            })
+    }
+
+    pub fn timestamp(&self, scan: usize) -> SystemTime {
+        self.started_at + self.elapsed(scan)
+    }
+
+    pub fn instant(&self, scan: usize) -> Instant {
+        self.started + self.elapsed(scan)
+    }
+}
+
+/// Tracks the time of each scan of a stream, as it is read.
+#[derive(Debug)]
+#[cfg(feature = "stream")]
+pub struct StreamClock {
+    scan_rate: f64,
+    // When the stream started, as (started_at, started)
+    start: Mutex<(SystemTime, Instant)>,
+    num_channels: usize,
+    scans: AtomicU64,
+
+    timer: Option<StreamedTimer>,
+    timer_state: Mutex<TimerState>,
+}
+
+// A timer in the scan list of a stream.
+#[derive(Clone, Copy, Debug)]
+#[cfg(feature = "stream")]
+struct StreamedTimer {
+    // The index of the timer in the scan list
+    index: usize,
+    frequency: f64,
+    wrap: i64,
+    // Whether the timer ticks too slowly to time each scan, so
+    // only corrects the drift of nominal times as it ticks over
+    coarse: bool,
+}
+
+#[derive(Debug, Default)]
+#[cfg(feature = "stream")]
+struct TimerState {
+    // The (scan, raw value) of the last valid timer reading
+    last: Option<(u64, u32)>,
+    ticks: u64,
+
+    // For coarse timers, the (scan, ticks) at which the
+    // timer first and last ticked over
+    first_tick: Option<(u64, u64)>,
+    last_tick: Option<(u64, u64)>,
+}
+
+#[cfg(feature = "stream")]
+impl StreamClock {
+    /// Starts the clock for a stream of `scan_list` at `scan_rate`.
+    pub fn new(scan_rate: f64, scan_list: &[i32]) -> Self {
+        let position = |address| scan_list.iter().position(|a| *a == address);
+
+        let timer = position(CORE_TIMER_ADDRESS)
+            .map(|index| StreamedTimer {
+                index,
+                frequency: CORE_TIMER_FREQUENCY,
+                wrap: TIMER_WRAP,
+                coarse: false,
+            })
+            .or_else(|| {
+                position(SYSTEM_TIMER_20HZ_ADDRESS).map(|index| StreamedTimer {
+                    index,
+                    frequency: SYSTEM_TIMER_20HZ_FREQUENCY,
+                    wrap: TIMER_WRAP,
+                    coarse: true,
+                })
+            });
+
+        StreamClock {
+            scan_rate,
+            start: Mutex::new((SystemTime::now(), Instant::now())),
+            num_channels: scan_list.len(),
+            scans: AtomicU64::new(0),
+            timer,
+            timer_state: Mutex::new(TimerState::default()),
+        }
+    }
+
+    /// Follows the full 32 bits of a streamed timer whose upper
+    /// 16 bits are captured, as given by a `CaptureLayout`.
+    pub fn with_captures(mut self, captures: &[Capture]) -> Self {
+        if let Some(timer) = &mut self.timer {
+            if captures.iter().any(|capture| capture.index == timer.index) {
+                timer.wrap = CAPTURED_TIMER_WRAP;
+            }
+        }
+
+        self
+    }
+
+    pub fn scan_rate(&self) -> f64 {
+        self.scan_rate
+    }
+
+    pub fn started_at(&self) -> SystemTime {
+        self.start().0
+    }
+
+    /// Re-bases the start of the clock on the time `scans` scans before now,
+    /// such as when the first scans of a triggered stream are read, as the
+    /// stream only started once its trigger fired.
+    pub fn rebase(&self, scans: u64) {
+        let before = Duration::from_secs_f64(scans as f64 / self.scan_rate);
+        let (now_at, now) = (SystemTime::now(), Instant::now());
+
+        *self.start.lock().unwrap_or_else(PoisonError::into_inner) = (
+            now_at.checked_sub(before).unwrap_or(now_at),
+            now.checked_sub(before).unwrap_or(now),
+        );
+    }
+
+    fn start(&self) -> (SystemTime, Instant) {
+        *self.start.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// The number of scans read so far.
+    pub fn scans(&self) -> u64 {
+        self.scans.load(Ordering::Relaxed)
+    }
+
+    /// Advances the clock past the scans in `data`, returning their timing.
+    /// Measured times are only collected if `measure` is set, though
+    /// any timer in the scan list is always followed.
+    pub fn advance(&self, data: &[f64], measure: bool) -> StreamTiming {
+        let num_scans = data.len().checked_div(self.num_channels).unwrap_or(0);
+        let first_scan = self.scans.fetch_add(num_scans as u64, Ordering::Relaxed);
+
+        let measured = self.timer.and_then(|timer| {
+            let mut state = self.timer_state.lock().ok()?;
+            let mut measured = Vec::with_capacity(if measure { num_scans } else { 0 });
+
+            for (i, scan) in data.chunks_exact(self.num_channels).enumerate() {
+                let scan_index = first_scan + i as u64;
+                let raw = timer_value(scan[timer.index], timer.wrap);
+
+                let elapsed = match raw {
+                    Some(raw) if !timer.coarse => {
+                        let ticks = self.unwrap_timer(&mut state, scan_index, raw, &timer);
+                        Duration::from_secs_f64(ticks as f64 / timer.frequency)
+                    }
+                    Some(raw) => {
+                        let previous = state.last.map(|_| state.ticks);
+                        let ticks = self.unwrap_timer(&mut state, scan_index, raw, &timer);
+                        if previous.is_some_and(|previous| previous != ticks) {
+                            state.first_tick.get_or_insert((scan_index, ticks));
+                            state.last_tick = Some((scan_index, ticks));
+                        }
+
+                        self.drift_corrected(&state, scan_index, &timer)
+                    }
+                    None => self.drift_corrected(&state, scan_index, &timer),
+                };
+
+                if measure {
+                    measured.push(elapsed);
+                }
+            }
+
+            measure.then_some(measured)
+        });
+
+        let (started_at, started) = self.start();
+
+        StreamTiming {
+            scan_rate: self.scan_rate,
+            started_at,
+            started,
+            first_scan,
+            measured,
+        }
+    }
+
+    // Returns the timer ticks since the stream started, using the nominal
+    // ticks between readings to resolve how many times the timer wrapped.
+    fn unwrap_timer(
+        &self,
+        state: &mut TimerState,
+        scan: u64,
+        raw: u32,
+        timer: &StreamedTimer,
+    ) -> u64 {
+        let expected =
+            |scans: u64| (scans as f64 * timer.frequency / self.scan_rate).round() as i64;
+        let wrap = timer.wrap;
+
+        state.ticks = match state.last {
+            None => expected(scan) as u64,
+            Some((last_scan, last_raw)) => {
+                let nominal = expected(scan - last_scan);
+                let mut error = (raw as i64 - last_raw as i64 - nominal).rem_euclid(wrap);
+                if error >= wrap / 2 {
+                    error -= wrap;
+                }
+
+                state.ticks + (nominal + error).max(0) as u64
+            }
+        };
+        state.last = Some((scan, raw));
+
+        state.ticks
+    }
+
+    // The nominal time of `scan`, corrected by the drift a coarse timer has
+    // measured between the scans at which it first and last ticked over.
+    // Nominal times are kept until then, and for fine timers.
+    fn drift_corrected(&self, state: &TimerState, scan: u64, timer: &StreamedTimer) -> Duration {
+        let nominal = |scans: u64| scans as f64 / self.scan_rate;
+
+        match (state.first_tick, state.last_tick) {
+            (Some((first_scan, first_ticks)), Some((last_scan, last_ticks))) => {
+                Duration::from_secs_f64(
+                    nominal(first_scan)
+                        + (last_ticks - first_ticks) as f64 / timer.frequency
+                        + nominal(scan.saturating_sub(last_scan)),
+                )
+            }
+            _ => Duration::from_secs_f64(nominal(scan)),
+        }
+    }
+}
+
+// A streamed timer value, or `None` for dummy and out of range values.
+#[cfg(feature = "stream")]
+fn timer_value(value: f64, wrap: i64) -> Option<u32> {
+    (0.0..wrap as f64).contains(&value).then_some(value as u32)
+}
+
+/// The address of `STREAM_DATA_CAPTURE_16`, which streams the upper 16 bits
+/// of the 32-bit register preceding it in the scan list.
+#[cfg(feature = "stream")]
+pub const STREAM_DATA_CAPTURE_16_ADDRESS: i32 = 4899;
+
+// LJM data types of the 32-bit integer registers which need capturing.
+#[cfg(feature = "stream")]
+pub(crate) const LJM_UINT32: i32 = 1;
+#[cfg(feature = "stream")]
+pub(crate) const LJM_INT32: i32 = 2;
+
+/// A 32-bit integer register in a stream's scan list,
+/// whose upper 16 bits are captured in the following slot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg(feature = "stream")]
+pub struct Capture {
+    /// The index of the register in the requested scan list.
+    pub index: usize,
+    /// Whether the register is an `LJM_INT32`, rather than an `LJM_UINT32`.
+    pub signed: bool,
+}
+
+#[cfg(feature = "stream")]
+impl Capture {
+    /// A capture for the register at `index`, if `data_type` (as returned by
+    /// `LJMLibrary::name_to_address`) is a 32-bit integer type.
+    pub fn for_type(index: usize, data_type: i32) -> Option<Self> {
+        match data_type {
+            LJM_UINT32 => Some(Capture {
+                index,
+                signed: false,
+            }),
+            LJM_INT32 => Some(Capture {
+                index,
+                signed: true,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Maps between the scan list requested for a stream, and the scan list
+/// streamed by the device, in which each captured 32-bit register is
+/// followed by `STREAM_DATA_CAPTURE_16`.
+///
+/// ```rust,ignore
+/// let layout = CaptureLayout::new(2, vec![Capture { index: 1, signed: false }]);
+///
+/// // [AIN0, CORE_TIMER] is streamed as [AIN0, CORE_TIMER, STREAM_DATA_CAPTURE_16]
+/// assert_eq!(layout.physical_len(), 3);
+/// ```
+#[derive(Clone, Debug, Default)]
+#[cfg(feature = "stream")]
+pub struct CaptureLayout {
+    num_channels: usize,
+    captures: Vec<Capture>,
+}
+
+#[cfg(feature = "stream")]
+impl CaptureLayout {
+    /// A layout for `num_channels` requested channels, with `captures`
+    /// in ascending order of index.
+    pub fn new(num_channels: usize, captures: Vec<Capture>) -> Self {
+        CaptureLayout {
+            num_channels,
+            captures,
+        }
+    }
+
+    pub fn captures(&self) -> &[Capture] {
+        &self.captures
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.captures.is_empty()
+    }
+
+    /// The number of channels requested.
+    pub fn logical_len(&self) -> usize {
+        self.num_channels
+    }
+
+    /// The number of channels streamed by the device.
+    pub fn physical_len(&self) -> usize {
+        self.num_channels + self.captures.len()
+    }
+
+    /// The scan list streamed by the device, for the requested `addresses`.
+    pub fn scan_list(&self, addresses: &[i32]) -> Vec<i32> {
+        let mut scan_list = Vec::with_capacity(addresses.len() + self.captures.len());
+
+        for (index, address) in addresses.iter().enumerate() {
+            scan_list.push(*address);
+            if self.captures.iter().any(|capture| capture.index == index) {
+                scan_list.push(STREAM_DATA_CAPTURE_16_ADDRESS);
+            }
+        }
+
+        scan_list
+    }
+
+    /// Combines each captured pair of `physical` data into its 32-bit value,
+    /// writing the requested channels to `logical`. Dummy and skipped values
+    /// in either half are kept as-is.
+    pub fn combine(&self, physical: &[f64], logical: &mut [f64]) {
+        let physical_scans = physical.chunks_exact(self.physical_len());
+        let logical_scans = logical.chunks_exact_mut(self.num_channels);
+
+        for (physical, logical) in physical_scans.zip(logical_scans) {
+            let mut captures = self.captures.iter().peekable();
+            let mut offset = 0;
+
+            for (index, value) in logical.iter_mut().enumerate() {
+                let low = physical[index + offset];
+
+                *value = match captures.next_if(|capture| capture.index == index) {
+                    Some(capture) => {
+                        offset += 1;
+                        combine_capture(low, physical[index + offset], capture.signed)
+                    }
+                    None => low,
+                };
+            }
+        }
+    }
+}
+
+// Joins the lower and upper 16 bits of a captured register.
+#[cfg(feature = "stream")]
+fn combine_capture(low: f64, high: f64, signed: bool) -> f64 {
+    for marker in [LJM_DUMMY_VALUE, LJM_SCAN_NOT_READ] {
+        if low == marker || high == marker {
+            return marker;
+        }
+    }
+
+    let value = ((high as u32) << 16) | (low as u32 & 0xFFFF);
+    if signed {
+        value as i32 as f64
+    } else {
+        value as f64
+    }
+}
+
+#[cfg(feature = "stream")]
+pub(crate) struct StreamCallback {
+    pub(crate) handle: i32,
+    pub(crate) scans_per_read: usize,
+    pub(crate) num_channels: usize,
+    pub(crate) layout: CaptureLayout,
+    pub(crate) callback: Mutex<Box<dyn FnMut(StreamBlock) + Send>>,
+    pub(crate) backlog: Arc<StreamBacklog>,
+    pub(crate) clock: Arc<StreamClock>,
+}
+
+#[derive(Debug, Default)]
+#[cfg(feature = "stream")]
+pub(crate) struct StreamBacklog {
+    device: AtomicI32,
+    ljm: AtomicI32,
+}
+
+#[cfg(feature = "stream")]
+impl StreamBacklog {
+    pub(crate) fn record(&self, dev_scan_backlog: i32, ljm_scan_backlog: i32) {
+        self.device.store(dev_scan_backlog, Ordering::Relaxed);
+        self.ljm.store(ljm_scan_backlog, Ordering::Relaxed);
+    }
+
+    pub(crate) fn get(&self) -> (i32, i32) {
+        (
+            self.device.load(Ordering::Relaxed),
+            self.ljm.load(Ordering::Relaxed),
+        )
+    }
+}
+
+/// The health of a running stream, from `LJMLibrary::stream_status`.
+///
+/// Healthy streams have backlogs near zero which are not growing.
+#[derive(Clone, Debug)]
+#[cfg(feature = "stream")]
+pub struct StreamStatus {
+    // Backlogs as of the most recent `stream_read` (or callback)
+    pub dev_scan_backlog: i32,
+    pub ljm_scan_backlog: i32,
+
+    /// `(buffer_size, bytes_in_buffer)` of the TCP receive buffer,
+    /// only present for Ethernet and WiFi connections.
+    pub tcp_receive_buffer: Option<(u32, u32)>,
+}
+
+#[cfg(feature = "stream")]
+impl StreamStatus {
+    /// The fraction of the TCP receive buffer in use, from 0 to 1.
+    pub fn tcp_buffer_fill(&self) -> Option<f64> {
+        self.tcp_receive_buffer
+            .filter(|(size, _)| *size > 0)
+            .map(|(size, used)| used as f64 / size as f64)
+    }
+}
+
+/// A reusable buffer sized to a handle's stream, which is read
+/// into without allocating, for use in high scan rate loops.
+///
+/// The stream must be started before the buffer is created,
+/// and a new buffer is required if the stream is restarted.
+#[derive(Clone, Debug)]
+#[cfg(feature = "stream")]
+pub struct StreamBuffer {
+    handle: i32,
+    num_channels: usize,
+    data: Vec<f64>,
+
+    dev_scan_backlog: i32,
+    ljm_scan_backlog: i32,
+}
+
+#[cfg(feature = "stream")]
+impl StreamBuffer {
+    pub fn new(handle: i32) -> Result<Self, LJMError> {
+        let (scans_per_read, num_channels) = LJMLibrary::stream_shape(handle)?;
+
+        Ok(StreamBuffer {
+            handle,
+            num_channels,
+            data: vec![0.0; scans_per_read * num_channels],
+            dev_scan_backlog: 0,
+            ljm_scan_backlog: 0,
+        })
+    }
+
+    /// Reads the next block of the stream into the buffer, overwriting
+    /// the previous block, and returns the interleaved data.
+    pub fn read(&mut self) -> Result<&[f64], LJMError> {
+        let (dev_scan_backlog, ljm_scan_backlog) =
+            LJMLibrary::stream_read_into(self.handle, &mut self.data)?;
+
+        self.dev_scan_backlog = dev_scan_backlog;
+        self.ljm_scan_backlog = ljm_scan_backlog;
+
+        Ok(&self.data)
+    }
+
+    /// The interleaved data from the most recent read.
+    pub fn data(&self) -> &[f64] {
+        &self.data
+    }
+
+    /// Iterates over each scan of the most recent read, with
+    /// one value per address in the scan list.
+    pub fn scans(&self) -> impl Iterator<Item = &[f64]> {
+        self.data.chunks_exact(self.num_channels.max(1))
+    }
+
+    /// The `(device, LJM)` scan backlogs from the most recent read.
+    pub fn backlogs(&self) -> (i32, i32) {
+        (self.dev_scan_backlog, self.ljm_scan_backlog)
+    }
+}
+
+/// The result of `LJMLibrary::stream_burst`, with data
+/// de-interleaved into one vector per scanned address.
+#[derive(Clone, Debug)]
+#[cfg(feature = "stream")]
+pub struct Burst {
+    // The scan rate the device actually scanned at
+    pub scan_rate: f64,
+    pub scan_list: Vec<i32>,
+    pub channels: Vec<Vec<f64>>,
+}
+
+#[cfg(feature = "stream")]
+impl Burst {
+    /// The values read for the given address, if it was scanned.
+    pub fn channel(&self, address: i32) -> Option<&[f64]> {
+        let index = self.scan_list.iter().position(|a| *a == address)?;
+        self.channels.get(index).map(|values| values.as_slice())
+    }
+
+    pub fn num_scans(&self) -> usize {
+        self.channels.first().map_or(0, |values| values.len())
+    }
+}
+
+// Separates `stream[0], ..., stream[N], stream[0], ...` into one vector per channel.
+#[cfg(feature = "stream")]
+pub(crate) fn deinterleave(data: &[f64], num_channels: usize) -> Vec<Vec<f64>> {
+    (0..num_channels)
+        .map(|i| data.iter().skip(i).step_by(num_channels).copied().collect())
+        .collect()
 }
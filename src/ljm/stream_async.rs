@@ -0,0 +1,56 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
+use tokio::sync::mpsc;
+
+use crate::{LJMError, StreamBlock};
+
+/// The default number of blocks buffered by `LJMLibrary::stream_async`.
+pub const DEFAULT_ASYNC_STREAM_CAPACITY: usize = 16;
+
+/// Yields stream blocks to async consumers, from `LJMLibrary::stream_async`.
+///
+/// Blocks are read on a blocking thread and buffered up to the capacity given,
+/// after which reads pause until the consumer catches up. Data then accumulates
+/// in the LJM buffer, which is reflected in each block's `ljm_scan_backlog`.
+///
+/// The stream ends after yielding the first error, such as once the
+/// stream is stopped with `stream_stop`.
+///
+/// ```rust,ignore
+/// let mut blocks = LJMLibrary::stream_async(handle, 16)?;
+///
+/// loop {
+///     tokio::select! {
+///         Some(block) = blocks.next() => { /* ... */ }
+///         _ = shutdown.recv() => break,
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AsyncStream {
+    handle: i32,
+    receiver: mpsc::Receiver<Result<StreamBlock, LJMError>>,
+}
+
+impl AsyncStream {
+    pub(crate) fn new(
+        handle: i32,
+        receiver: mpsc::Receiver<Result<StreamBlock, LJMError>>,
+    ) -> Self {
+        AsyncStream { handle, receiver }
+    }
+
+    pub fn handle(&self) -> i32 {
+        self.handle
+    }
+}
+
+impl Stream for AsyncStream {
+    type Item = Result<StreamBlock, LJMError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
@@ -0,0 +1,494 @@
+use std::collections::BTreeMap;
+
+use crate::{DeviceType, LJMError};
+
+// `AIN#_NEGATIVE_CH` value for single-ended readings.
+const SINGLE_ENDED: u32 = 199;
+
+// The largest `STREAM_SETTLING_US`, in microseconds.
+const MAX_SETTLING_US: f64 = 4400.0;
+
+// Library configs controlling how `LJM_eStreamRead` waits for scans.
+const LJM_STREAM_RECEIVE_TIMEOUT_MODE: &str = "LJM_STREAM_RECEIVE_TIMEOUT_MODE";
+const LJM_STREAM_RECEIVE_TIMEOUT_MODE_MANUAL: i32 = 2;
+const LJM_STREAM_RECEIVE_TIMEOUT_MS: &str = "LJM_STREAM_RECEIVE_TIMEOUT_MS";
+
+/// The clock which paces the stream, written to `STREAM_CLOCK_SOURCE`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StreamClockSource {
+    /// The device's internal crystal.
+    #[default]
+    Internal,
+    /// An external clock on CIO3, with one scan per rising edge.
+    External,
+}
+
+impl From<StreamClockSource> for f64 {
+    fn from(value: StreamClockSource) -> Self {
+        match value {
+            StreamClockSource::Internal => 0.0,
+            StreamClockSource::External => 2.0,
+        }
+    }
+}
+
+/// The edge of a trigger line which starts a triggered stream.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TriggerEdge {
+    Rising,
+    Falling,
+}
+
+impl TriggerEdge {
+    // The `DIO#_EF_CONFIG_A` of a Conditional Reset which detects this edge.
+    fn config_a(&self) -> f64 {
+        match self {
+            TriggerEdge::Rising => 1.0,
+            TriggerEdge::Falling => 0.0,
+        }
+    }
+}
+
+// The `DIO#_EF_INDEX` of Conditional Reset, which triggers a stream on
+// the edge selected by `DIO#_EF_CONFIG_A`.
+const CONDITIONAL_RESET_EF_INDEX: f64 = 12.0;
+
+/// A digital edge on which the device starts streaming, such that
+/// scans are only collected from the moment the trigger fires.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StreamTrigger {
+    pub dio: u32,
+    pub edge: TriggerEdge,
+}
+
+impl StreamTrigger {
+    pub fn new(dio: u32, edge: TriggerEdge) -> Self {
+        StreamTrigger { dio, edge }
+    }
+
+    /// The `STREAM_TRIGGER_INDEX` for this trigger line.
+    pub fn trigger_index(&self) -> u32 {
+        2000 + self.dio
+    }
+
+    // Configures the line's extended feature to detect the edge.
+    fn registers(&self) -> Vec<(String, f64)> {
+        vec![
+            (format!("DIO{}_EF_ENABLE", self.dio), 0.0),
+            (
+                format!("DIO{}_EF_INDEX", self.dio),
+                CONDITIONAL_RESET_EF_INDEX,
+            ),
+            (format!("DIO{}_EF_CONFIG_A", self.dio), self.edge.config_a()),
+            (format!("DIO{}_EF_ENABLE", self.dio), 1.0),
+        ]
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct AinConfig {
+    range: Option<f64>,
+    negative_channel: Option<u32>,
+}
+
+/// The configuration of a stream, applied by `LJMLibrary::stream_start_config`.
+///
+/// Every `STREAM_*` register is written before the stream is started (with
+/// defaults for any not given), so no configuration is left over from a
+/// previous stream. The exception is `STREAM_EXTERNAL_CLOCK_DIVISOR`, which
+/// is only written for externally clocked streams, or when set.
+///
+/// ```rust,ignore
+/// let config = StreamConfig::new(100, 1000.0)
+///     .channel("AIN0")
+///     .channel("AIN2")
+///     .range(0, 1.0)
+///     .negative_channel(2, 3)
+///     .resolution_index(4);
+///
+/// let scan_rate = LJMLibrary::stream_start_config(handle, config)?;
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct StreamConfig {
+    scans_per_read: i32,
+    scan_rate: f64,
+    channels: Vec<String>,
+
+    settling_us: f64,
+    resolution_index: u32,
+    buffer_size_bytes: Option<u32>,
+    clock_source: StreamClockSource,
+    external_clock_divisor: Option<u32>,
+    trigger_index: u32,
+    trigger: Option<StreamTrigger>,
+    binary: bool,
+    ain: BTreeMap<u32, AinConfig>,
+}
+
+impl StreamConfig {
+    pub fn new(scans_per_read: i32, scan_rate: f64) -> Self {
+        StreamConfig {
+            scans_per_read,
+            scan_rate,
+            channels: Vec::new(),
+            settling_us: 0.0,
+            resolution_index: 0,
+            buffer_size_bytes: None,
+            clock_source: StreamClockSource::default(),
+            external_clock_divisor: None,
+            trigger_index: 0,
+            trigger: None,
+            binary: false,
+            ain: BTreeMap::new(),
+        }
+    }
+
+    /// Adds a register to the scan list, such as `AIN0`.
+    pub fn channel<T: ToString>(mut self, name: T) -> Self {
+        self.channels.push(name.to_string());
+        self
+    }
+
+    /// Sets `STREAM_SETTLING_US`, where 0 selects automatic settling.
+    pub fn settling_us(mut self, settling_us: f64) -> Self {
+        self.settling_us = settling_us;
+        self
+    }
+
+    /// Sets `STREAM_RESOLUTION_INDEX`, where 0 selects the device default.
+    pub fn resolution_index(mut self, resolution_index: u32) -> Self {
+        self.resolution_index = resolution_index;
+        self
+    }
+
+    /// Sets `STREAM_BUFFER_SIZE_BYTES`, which must be a power of 2.
+    pub fn buffer_size_bytes(mut self, buffer_size_bytes: u32) -> Self {
+        self.buffer_size_bytes = Some(buffer_size_bytes);
+        self
+    }
+
+    pub fn clock_source(mut self, clock_source: StreamClockSource) -> Self {
+        self.clock_source = clock_source;
+        self
+    }
+
+    /// Sets `STREAM_EXTERNAL_CLOCK_DIVISOR`, such that an externally
+    /// clocked stream scans once per `divisor` clock pulses.
+    pub fn external_clock_divisor(mut self, divisor: u32) -> Self {
+        self.external_clock_divisor = Some(divisor);
+        self
+    }
+
+    /// Sets `STREAM_TRIGGER_INDEX`, where 0 starts the stream immediately.
+    pub fn trigger_index(mut self, trigger_index: u32) -> Self {
+        self.trigger_index = trigger_index;
+        self
+    }
+
+    /// Starts the stream on an edge of a DIO line, configuring
+    /// the line and `STREAM_TRIGGER_INDEX` to do so.
+    pub fn trigger(mut self, trigger: StreamTrigger) -> Self {
+        self.trigger_index = trigger.trigger_index();
+        self.trigger = Some(trigger);
+        self
+    }
+
+    /// Streams AIN channels as raw 16-bit counts (`LJM_STREAM_AIN_BINARY`),
+    /// reading the device's calibration constants when the stream is started.
+    /// Every channel must then be an AIN channel, given by name.
+    ///
+    /// Counts are read with `LJMLibrary::stream_read_raw`, and converted to
+    /// volts with the `CalibrationTable` from `LJMLibrary::stream_calibration`.
+    pub fn binary(mut self, binary: bool) -> Self {
+        self.binary = binary;
+        self
+    }
+
+    /// Sets `AIN#_RANGE` for AIN `ain`, as the positive bound in volts (such as `10.0` for ±10V).
+    pub fn range(mut self, ain: u32, range: f64) -> Self {
+        self.ain.entry(ain).or_default().range = Some(range);
+        self
+    }
+
+    /// Sets `AIN#_NEGATIVE_CH` for AIN `ain`, for a differential reading.
+    pub fn negative_channel(mut self, ain: u32, negative_channel: u32) -> Self {
+        self.ain.entry(ain).or_default().negative_channel = Some(negative_channel);
+        self
+    }
+
+    pub fn scans_per_read(&self) -> i32 {
+        self.scans_per_read
+    }
+
+    pub fn scan_rate(&self) -> f64 {
+        self.scan_rate
+    }
+
+    pub fn channels(&self) -> &[String] {
+        &self.channels
+    }
+
+    /// Whether scans are paced by pulses on CIO3, rather than the device.
+    pub fn is_externally_clocked(&self) -> bool {
+        self.clock_source == StreamClockSource::External
+    }
+
+    /// Whether the stream waits for a trigger before collecting scans.
+    pub fn is_triggered(&self) -> bool {
+        self.trigger_index != 0
+    }
+
+    /// Whether AIN channels are streamed as raw counts, rather than volts.
+    pub fn is_binary(&self) -> bool {
+        self.binary
+    }
+
+    /// The `(register, value)` pairs written before the stream is started.
+    pub fn registers(&self) -> Vec<(String, f64)> {
+        let mut registers = self
+            .trigger
+            .map(|trigger| trigger.registers())
+            .unwrap_or_default();
+
+        registers.extend([
+            (
+                "STREAM_TRIGGER_INDEX".to_string(),
+                self.trigger_index as f64,
+            ),
+            ("STREAM_CLOCK_SOURCE".to_string(), self.clock_source.into()),
+            ("STREAM_SETTLING_US".to_string(), self.settling_us),
+            (
+                "STREAM_RESOLUTION_INDEX".to_string(),
+                self.resolution_index as f64,
+            ),
+        ]);
+
+        if self.clock_source == StreamClockSource::External || self.external_clock_divisor.is_some()
+        {
+            registers.push((
+                "STREAM_EXTERNAL_CLOCK_DIVISOR".to_string(),
+                self.external_clock_divisor.unwrap_or(1) as f64,
+            ));
+        }
+
+        // 0 selects the device's default buffer size.
+        registers.push((
+            "STREAM_BUFFER_SIZE_BYTES".to_string(),
+            self.buffer_size_bytes.unwrap_or(0) as f64,
+        ));
+
+        for (ain, config) in &self.ain {
+            if let Some(range) = config.range {
+                registers.push((format!("AIN{}_RANGE", ain), range));
+            }
+            if let Some(negative_channel) = config.negative_channel {
+                registers.push((format!("AIN{}_NEGATIVE_CH", ain), negative_channel as f64));
+            }
+        }
+
+        registers
+    }
+
+    /// The `(config, value)` pairs of LJM library configs set while the stream
+    /// is started, in the order they are written. Their previous values are
+    /// restored in the reverse order, since writing `LJM_STREAM_RECEIVE_TIMEOUT_MS`
+    /// also sets `LJM_STREAM_RECEIVE_TIMEOUT_MODE` to manual.
+    ///
+    /// Reads should not time out when scans depend on external signals, as
+    /// the timeout LJM calculates assumes the scan rate is kept. A manual
+    /// timeout of 0 waits indefinitely.
+    pub fn library_configs(&self) -> Vec<(&'static str, f64)> {
+        if !self.is_triggered() && !self.is_externally_clocked() {
+            return Vec::new();
+        }
+
+        vec![
+            (
+                LJM_STREAM_RECEIVE_TIMEOUT_MODE,
+                LJM_STREAM_RECEIVE_TIMEOUT_MODE_MANUAL as f64,
+            ),
+            (LJM_STREAM_RECEIVE_TIMEOUT_MS, 0.0),
+        ]
+    }
+
+    /// Checks the configuration is supported by `device_type`. Only general
+    /// checks are made for emulated and unknown devices.
+    pub fn validate(&self, device_type: &DeviceType) -> Result<(), LJMError> {
+        if self.scans_per_read <= 0 {
+            return Err(invalid("scans_per_read must be positive"));
+        }
+        if self.scan_rate.is_nan() || self.scan_rate <= 0.0 {
+            return Err(invalid("scan_rate must be positive"));
+        }
+        if self.channels.is_empty() {
+            return Err(invalid("no channels to stream"));
+        }
+        if !(0.0..=MAX_SETTLING_US).contains(&self.settling_us) {
+            return Err(invalid(format!(
+                "STREAM_SETTLING_US of {} is not within 0 to {}",
+                self.settling_us, MAX_SETTLING_US
+            )));
+        }
+        if self.external_clock_divisor == Some(0) {
+            return Err(invalid("STREAM_EXTERNAL_CLOCK_DIVISOR must be positive"));
+        }
+        if let Some(size) = self.buffer_size_bytes {
+            if !size.is_power_of_two() || !(256..=32768).contains(&size) {
+                return Err(invalid(format!(
+                    "STREAM_BUFFER_SIZE_BYTES of {} is not a power of 2 from 256 to 32768",
+                    size
+                )));
+            }
+        }
+
+        if self.binary {
+            if let Some(channel) = self.channels.iter().find(|c| !is_ain_channel(c)) {
+                return Err(invalid(format!(
+                    "{} cannot be streamed in binary mode, which only AIN channels may be",
+                    channel
+                )));
+            }
+        }
+
+        let Some(limits) = DeviceLimits::for_device(device_type) else {
+            if self.binary {
+                return Err(invalid(format!(
+                    "{:?} devices cannot be calibrated for binary streams",
+                    device_type
+                )));
+            }
+
+            return match device_type {
+                DeviceType::EMULATED(_) | DeviceType::UNKNOWN(_) => Ok(()),
+                _ => Err(invalid(format!("{:?} devices cannot stream", device_type))),
+            };
+        };
+
+        if self.binary && !limits.calibrated {
+            return Err(invalid(format!(
+                "{:?} devices cannot be calibrated for binary streams",
+                device_type
+            )));
+        }
+
+        if self.resolution_index > limits.max_resolution_index {
+            return Err(invalid(format!(
+                "STREAM_RESOLUTION_INDEX of {} exceeds {} for {:?}",
+                self.resolution_index, limits.max_resolution_index, device_type
+            )));
+        }
+
+        if self.clock_source == StreamClockSource::External && !limits.external_clock {
+            return Err(invalid(format!(
+                "{:?} has no CIO3 for an external clock",
+                device_type
+            )));
+        }
+
+        if self.trigger_index != 0
+            && !limits
+                .trigger_dio
+                .iter()
+                .any(|dio| self.trigger_index == 2000 + dio)
+        {
+            return Err(invalid(format!(
+                "STREAM_TRIGGER_INDEX of {} is not a DIO_EF on {:?}",
+                self.trigger_index, device_type
+            )));
+        }
+
+        for (ain, config) in &self.ain {
+            if *ain >= limits.num_ain {
+                return Err(invalid(format!("{:?} has no AIN{}", device_type, ain)));
+            }
+
+            if let Some(range) = config.range {
+                if !limits.ranges.contains(&range) {
+                    return Err(invalid(format!(
+                        "AIN{}_RANGE of {} is not supported by {:?}",
+                        ain, range, device_type
+                    )));
+                }
+            }
+
+            if let Some(negative_channel) = config.negative_channel {
+                let differential = limits.differential
+                    && ain % 2 == 0
+                    && *ain < limits.num_ain - 1
+                    && negative_channel == ain + 1;
+
+                if negative_channel != SINGLE_ENDED && !differential {
+                    return Err(invalid(format!(
+                        "AIN{}_NEGATIVE_CH of {} is not supported by {:?}",
+                        ain, negative_channel, device_type
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// What each device supports when streaming.
+struct DeviceLimits {
+    num_ain: u32,
+    max_resolution_index: u32,
+    ranges: &'static [f64],
+    differential: bool,
+    // The DIO lines supporting Conditional Reset, so may trigger a stream.
+    trigger_dio: &'static [u32],
+    // Whether scans may be paced by an external clock on CIO3.
+    external_clock: bool,
+    // Whether `CalibrationTable::read` supports the device.
+    calibrated: bool,
+}
+
+impl DeviceLimits {
+    fn for_device(device_type: &DeviceType) -> Option<Self> {
+        match device_type {
+            // T4 ranges are fixed by the channel, so cannot be set.
+            DeviceType::T4 => Some(DeviceLimits {
+                num_ain: 12,
+                max_resolution_index: 5,
+                ranges: &[],
+                differential: false,
+                trigger_dio: &[4, 5, 6, 7, 8, 9],
+                external_clock: true,
+                calibrated: false,
+            }),
+            DeviceType::T7 => Some(DeviceLimits {
+                num_ain: 14,
+                max_resolution_index: 8,
+                ranges: &[10.0, 1.0, 0.1, 0.01],
+                differential: true,
+                trigger_dio: &[0, 1, 2, 3, 6, 7],
+                external_clock: true,
+                calibrated: true,
+            }),
+            // T8 inputs are isolated, so always single-ended.
+            DeviceType::T8 => Some(DeviceLimits {
+                num_ain: 8,
+                max_resolution_index: 16,
+                ranges: &[
+                    11.0, 9.6, 4.8, 2.4, 1.2, 0.6, 0.3, 0.15, 0.075, 0.036, 0.018,
+                ],
+                differential: false,
+                trigger_dio: &[0, 1, 2, 3, 4, 5, 6, 7],
+                external_clock: false,
+                calibrated: false,
+            }),
+            _ => None,
+        }
+    }
+}
+
+// Whether `name` is an AIN channel, such as `AIN0`, which binary streams return as counts.
+pub(crate) fn is_ain_channel(name: &str) -> bool {
+    name.strip_prefix("AIN")
+        .is_some_and(|ain| !ain.is_empty() && ain.bytes().all(|b| b.is_ascii_digit()))
+}
+
+fn invalid<T: ToString>(reason: T) -> LJMError {
+    LJMError::InvalidStreamConfig(reason.to_string())
+}
@@ -0,0 +1,152 @@
+use crate::{LJMError, LJMLibrary};
+
+/// The number of values written to the stream-out queue by each `feed`.
+pub const DEFAULT_APERIODIC_CHUNK_SIZE: usize = 512;
+
+/// Plays back a non-repeating signal through an aperiodic stream-out,
+/// alongside the usual `stream_read` loop on the same handle.
+///
+/// ```rust,ignore
+/// let mut out = AperiodicStreamOut::new(handle, 0, "DAC0", scan_rate)?;
+/// LJMLibrary::stream_start_addr(handle, scans_per_read, scan_rate, vec!["AIN0"])?;
+///
+/// let mut samples = recording.into_iter();
+/// while let Some(remaining) = out.feed(&mut samples)? {
+///     let block = LJMLibrary::stream_read_detailed(handle)?;
+///     out.consumed(block.scans);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AperiodicStreamOut {
+    handle: i32,
+    stream_out_index: i32,
+    queue: AperiodicQueue,
+
+    // Reused between writes to avoid allocating for each chunk
+    chunk: Vec<f64>,
+}
+
+impl AperiodicStreamOut {
+    /// Initializes the stream-out, which must happen before the stream is started.
+    pub fn new<T: ToString>(
+        handle: i32,
+        stream_out_index: i32,
+        target: T,
+        scan_rate: f64,
+    ) -> Result<Self, LJMError> {
+        LJMLibrary::initialize_aperiodic_stream_out(handle, stream_out_index, target, scan_rate)?;
+
+        Ok(AperiodicStreamOut {
+            handle,
+            stream_out_index,
+            queue: AperiodicQueue::new(DEFAULT_APERIODIC_CHUNK_SIZE),
+            chunk: Vec::with_capacity(DEFAULT_APERIODIC_CHUNK_SIZE),
+        })
+    }
+
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.queue = AperiodicQueue {
+            chunk_size: chunk_size.max(1),
+            ..self.queue
+        };
+        self
+    }
+
+    /// Writes the values directly to the stream-out queue,
+    /// returning the number of values which can still be queued.
+    pub fn write(&mut self, values: &[f64]) -> Result<i32, LJMError> {
+        let remaining =
+            LJMLibrary::write_aperiodic_stream_out(self.handle, self.stream_out_index, values)?;
+
+        self.queue.written(remaining);
+        Ok(remaining)
+    }
+
+    /// Writes up to one chunk of samples from the iterator, returning the
+    /// number of values which can still be queued, or `None` once the
+    /// iterator is exhausted.
+    ///
+    /// No more than the known queue space is taken from the iterator, so
+    /// a return of `Some(0)` means the queue is full. Feeding into a full
+    /// queue takes no samples and writes nothing, so the caller should
+    /// report the scans read since with `consumed` before feeding again.
+    pub fn feed<I: Iterator<Item = f64>>(
+        &mut self,
+        samples: &mut I,
+    ) -> Result<Option<i32>, LJMError> {
+        let limit = self.queue.limit();
+        if limit == 0 {
+            return Ok(Some(0));
+        }
+
+        let mut chunk = std::mem::take(&mut self.chunk);
+        chunk.clear();
+        chunk.extend(samples.by_ref().take(limit));
+
+        let result = if chunk.is_empty() {
+            Ok(None)
+        } else {
+            self.write(&chunk).map(Some)
+        };
+
+        self.chunk = chunk;
+        result
+    }
+
+    /// Records that `scans` scans of the stream have been read, each of
+    /// which has taken one value from the stream-out queue.
+    pub fn consumed(&mut self, scans: usize) {
+        self.queue.consumed(scans);
+    }
+
+    /// The number of values which can be queued, as of the last
+    /// write and the scans `consumed` since.
+    pub fn queue_vals_remaining(&self) -> Option<i32> {
+        self.queue.remaining()
+    }
+}
+
+/// Tracks the space in an aperiodic stream-out queue, from the space
+/// LJM reports on each write and the scans consumed since.
+///
+/// The device takes one value from the queue per scan, though LJM may
+/// move values to the device sooner, so the space is underestimated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AperiodicQueue {
+    chunk_size: usize,
+    remaining: Option<i32>,
+}
+
+impl AperiodicQueue {
+    /// A queue of unknown space, written `chunk_size` values at a time.
+    pub fn new(chunk_size: usize) -> Self {
+        AperiodicQueue {
+            chunk_size: chunk_size.max(1),
+            remaining: None,
+        }
+    }
+
+    /// The number of values which may be written next.
+    pub fn limit(&self) -> usize {
+        match self.remaining {
+            Some(remaining) => self.chunk_size.min(remaining.max(0) as usize),
+            None => self.chunk_size,
+        }
+    }
+
+    /// Records the space LJM reported after a write.
+    pub fn written(&mut self, remaining: i32) {
+        self.remaining = Some(remaining);
+    }
+
+    /// Records that `scans` values have been taken from the queue.
+    pub fn consumed(&mut self, scans: usize) {
+        if let Some(remaining) = &mut self.remaining {
+            *remaining = remaining.saturating_add(scans.min(i32::MAX as usize) as i32);
+        }
+    }
+
+    pub fn remaining(&self) -> Option<i32> {
+        self.remaining
+    }
+}
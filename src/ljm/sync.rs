@@ -0,0 +1,330 @@
+use crate::{
+    DeviceType, LJMError, LJMLibrary, StreamBlock, StreamClockSource, StreamConfig, StreamFrame,
+};
+
+/// A square wave output on a DIO line by a device's `DIO_EF_CLOCK0`,
+/// used as the external stream clock of a `SyncedStreamGroup`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PwmClock {
+    pub dio: u32,
+    pub frequency: f64,
+}
+
+impl PwmClock {
+    pub fn new(dio: u32, frequency: f64) -> Self {
+        PwmClock { dio, frequency }
+    }
+
+    /// The frequency of `DIO_EF_CLOCK0` for the device type, in Hz.
+    pub fn core_frequency(device_type: &DeviceType) -> Option<f64> {
+        match device_type {
+            DeviceType::T4 | DeviceType::T7 => Some(80_000_000.0),
+            DeviceType::T8 => Some(100_000_000.0),
+            _ => None,
+        }
+    }
+
+    /// Checks the clock's DIO line supports PWM output on the device type.
+    pub fn validate(&self, device_type: &DeviceType) -> Result<(), LJMError> {
+        let pwm_dio: &[u32] = match device_type {
+            DeviceType::T4 => &[6, 7],
+            DeviceType::T7 => &[0, 2, 3, 4, 5],
+            DeviceType::T8 => &[0, 1, 2, 3, 4, 5, 6, 7],
+            _ => &[],
+        };
+
+        if !pwm_dio.contains(&self.dio) {
+            return Err(LJMError::InvalidStreamConfig(format!(
+                "DIO{} cannot output a clock on {:?}",
+                self.dio, device_type
+            )));
+        }
+
+        Ok(())
+    }
+
+    // The number of core clock ticks in each period.
+    fn roll_value(&self, core_frequency: f64) -> f64 {
+        (core_frequency / self.frequency).round().max(2.0)
+    }
+
+    /// The frequency actually output, as the period is a whole number of core clock ticks.
+    pub fn actual_frequency(&self, core_frequency: f64) -> f64 {
+        core_frequency / self.roll_value(core_frequency)
+    }
+
+    /// The `(register, value)` pairs which start the clock, at a 50% duty cycle.
+    pub fn registers(&self, core_frequency: f64) -> Vec<(String, f64)> {
+        let roll_value = self.roll_value(core_frequency);
+
+        vec![
+            ("DIO_EF_CLOCK0_ENABLE".to_string(), 0.0),
+            ("DIO_EF_CLOCK0_DIVISOR".to_string(), 1.0),
+            ("DIO_EF_CLOCK0_ROLL_VALUE".to_string(), roll_value),
+            ("DIO_EF_CLOCK0_ENABLE".to_string(), 1.0),
+            (format!("DIO{}_EF_ENABLE", self.dio), 0.0),
+            (format!("DIO{}_EF_INDEX", self.dio), 0.0),
+            (
+                format!("DIO{}_EF_CONFIG_A", self.dio),
+                (roll_value / 2.0).floor(),
+            ),
+            (format!("DIO{}_EF_ENABLE", self.dio), 1.0),
+        ]
+    }
+
+    /// The `(register, value)` pairs which stop the clock.
+    pub fn stop_registers(&self) -> Vec<(String, f64)> {
+        vec![
+            (format!("DIO{}_EF_ENABLE", self.dio), 0.0),
+            ("DIO_EF_CLOCK0_ENABLE".to_string(), 0.0),
+        ]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct SyncedDevice {
+    handle: i32,
+    label: String,
+    channels: Vec<String>,
+}
+
+/// Streams from several devices on a shared external clock, such that their
+/// scans are aligned, and merges their reads into a single `StreamFrame`.
+///
+/// The master outputs the clock on a DIO line (with `PwmClock`), which must be
+/// wired to CIO3 of every device in the group, including the master itself.
+/// Streams are started on every device before the clock, so all begin on
+/// the same pulse.
+///
+/// ```rust,ignore
+/// let mut group = SyncedStreamGroup::new(master, PwmClock::new(0, 1000.0), 100)
+///     .device(master, "a", vec!["AIN0"])
+///     .device(other, "b", vec!["AIN0", "AIN1"]);
+///
+/// group.start()?;
+/// let frame = group.read()?;
+/// let b_ain1 = frame.channel("b/AIN1");
+/// ```
+#[derive(Debug)]
+pub struct SyncedStreamGroup {
+    master: i32,
+    clock: PwmClock,
+    scans_per_read: i32,
+    devices: Vec<SyncedDevice>,
+
+    // The (names, addresses) of the merged frame, once started
+    names: Vec<String>,
+    addresses: Vec<i32>,
+    started: bool,
+
+    // The block read from each device which is yet to be merged
+    pending: Vec<Option<StreamBlock>>,
+}
+
+impl SyncedStreamGroup {
+    pub fn new(master: i32, clock: PwmClock, scans_per_read: i32) -> Self {
+        SyncedStreamGroup {
+            master,
+            clock,
+            scans_per_read,
+            devices: Vec::new(),
+            names: Vec::new(),
+            addresses: Vec::new(),
+            started: false,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Adds a device to the group, with its channels named `label/channel` in merged frames.
+    pub fn device<L: ToString, T: ToString>(
+        mut self,
+        handle: i32,
+        label: L,
+        channels: Vec<T>,
+    ) -> Self {
+        self.devices.push(SyncedDevice {
+            handle,
+            label: label.to_string(),
+            channels: channels.iter().map(|c| c.to_string()).collect(),
+        });
+        self
+    }
+
+    pub fn handles(&self) -> Vec<i32> {
+        self.devices.iter().map(|device| device.handle).collect()
+    }
+
+    /// Starts an externally clocked stream on each device, then the master's clock.
+    /// Returns the actual scan rate of the group.
+    pub fn start(&mut self) -> Result<f64, LJMError> {
+        if !self.clock.frequency.is_finite() || self.clock.frequency <= 0.0 {
+            return Err(LJMError::InvalidStreamConfig(format!(
+                "clock frequency of {} Hz must be positive",
+                self.clock.frequency
+            )));
+        }
+
+        let device_type = LJMLibrary::get_handle_info(self.master)?.device_type;
+        let core_frequency = PwmClock::core_frequency(&device_type).ok_or_else(|| {
+            LJMError::InvalidStreamConfig(format!("{:?} cannot output a clock", device_type))
+        })?;
+        self.clock.validate(&device_type)?;
+        let scan_rate = self.clock.actual_frequency(core_frequency);
+
+        // The clock must not run until every stream is waiting on it.
+        LJMLibrary::write_names(self.master, &self.clock.stop_registers())?;
+
+        self.names.clear();
+        self.addresses.clear();
+
+        for device in &self.devices {
+            let config = device
+                .channels
+                .iter()
+                .fold(
+                    StreamConfig::new(self.scans_per_read, scan_rate),
+                    |config, channel| config.channel(channel),
+                )
+                .clock_source(StreamClockSource::External);
+
+            let channels = LJMLibrary::stream_start_config(device.handle, config)
+                .and_then(|_| LJMLibrary::stream_channels(device.handle));
+
+            let (names, addresses) = match channels {
+                Ok(channels) => channels,
+                Err(error) => {
+                    // The error starting the stream takes precedence.
+                    let _ = self.stop_streams();
+                    return Err(error);
+                }
+            };
+            self.names.extend(
+                names
+                    .iter()
+                    .map(|name| format!("{}/{}", device.label, name)),
+            );
+            self.addresses.extend(addresses);
+        }
+
+        self.started = true;
+        self.pending = vec![None; self.devices.len()];
+
+        if let Err(error) =
+            LJMLibrary::write_names(self.master, &self.clock.registers(core_frequency))
+        {
+            let _ = self.stop();
+            return Err(error);
+        }
+
+        Ok(scan_rate)
+    }
+
+    /// Reads from every device, merging each scan into a single frame in device order.
+    ///
+    /// Backlogs are the largest of any device, and timing is taken from the master.
+    ///
+    /// If reading from a device fails, the blocks already read from the others are
+    /// kept, to be merged once a later read has a block from every device. Until
+    /// then (or while any device returns an empty block), the frame is empty.
+    /// As every device scans on the same clock, reading a different number of
+    /// scans from each means the group is out of sync, so it is stopped.
+    pub fn read(&mut self) -> Result<StreamFrame, LJMError> {
+        if !self.started {
+            return Err(LJMError::StreamNotStarted);
+        }
+
+        for (device, pending) in self.devices.iter().zip(&mut self.pending) {
+            if pending.is_none() {
+                let block = LJMLibrary::stream_read_detailed(device.handle)?;
+                *pending = Some(block).filter(|block| block.scans > 0);
+            }
+        }
+
+        if self.pending.iter().any(Option::is_none) {
+            return Ok(StreamFrame::new(
+                StreamBlock::empty(true),
+                self.names.clone(),
+                self.addresses.clone(),
+            ));
+        }
+
+        let mut blocks: Vec<StreamBlock> = self.pending.iter_mut().flat_map(Option::take).collect();
+
+        let scans = blocks.first().map_or(0, |block| block.scans);
+        if let Some(block) = blocks.iter().find(|block| block.scans != scans) {
+            let width = block.data.len() / block.scans;
+            let error = LJMError::BufferSizeMismatch(scans * width, block.data.len());
+            let _ = self.stop();
+            return Err(error);
+        }
+
+        let num_channels = self.addresses.len();
+        let mut data = Vec::with_capacity(scans * num_channels);
+
+        for scan in 0..scans {
+            for block in &blocks {
+                let width = block.data.len().checked_div(block.scans).unwrap_or(0);
+                let values = block.data.get(scan * width..(scan + 1) * width).ok_or(
+                    LJMError::BufferSizeMismatch(scans * width, block.data.len()),
+                )?;
+
+                data.extend_from_slice(values);
+            }
+        }
+
+        let timing = self
+            .devices
+            .iter()
+            .position(|device| device.handle == self.master)
+            .and_then(|index| blocks[index].timing.take());
+
+        let block = StreamBlock::new(
+            data,
+            num_channels,
+            blocks.iter().map(|b| b.dev_scan_backlog).max().unwrap_or(0),
+            blocks.iter().map(|b| b.ljm_scan_backlog).max().unwrap_or(0),
+            timing,
+        );
+
+        Ok(StreamFrame::new(
+            block,
+            self.names.clone(),
+            self.addresses.clone(),
+        ))
+    }
+
+    /// Stops the master's clock, then the stream on each device.
+    pub fn stop(&mut self) -> Result<(), LJMError> {
+        let clock = LJMLibrary::write_names(self.master, &self.clock.stop_registers());
+        let streams = self.stop_streams();
+
+        clock.and(streams)
+    }
+
+    // Stops every stream which is running, returning the first error.
+    fn stop_streams(&mut self) -> Result<(), LJMError> {
+        self.started = false;
+        self.pending.clear();
+
+        let mut result = Ok(());
+
+        // Every stream is stopped, even if stopping another failed.
+        for device in &self.devices {
+            if LJMLibrary::is_stream_active(device.handle) {
+                if let Err(error) = LJMLibrary::stream_stop(device.handle) {
+                    result = result.and(Err(error));
+                }
+            }
+        }
+
+        result
+    }
+}
+
+impl Drop for SyncedStreamGroup {
+    fn drop(&mut self) {
+        if self.started {
+            let _ = self.stop();
+        }
+    }
+}
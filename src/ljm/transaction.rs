@@ -0,0 +1,223 @@
+use crate::{LJMError, LJMLibrary};
+
+// Access types for each frame, as given by `LJM_READ` and `LJM_WRITE`.
+pub(crate) const LJM_READ: i32 = 0;
+pub(crate) const LJM_WRITE: i32 = 1;
+
+#[derive(Clone, Debug)]
+pub(crate) enum Target {
+    Name(String),
+    Address(i32, i32),
+}
+
+#[derive(Clone, Debug)]
+pub(crate) enum Step {
+    Write(Target, Vec<f64>),
+    Read(Target),
+    ReadArray(Target, usize),
+}
+
+/// A mixed sequence of reads and writes, executed in a
+/// single `LJM_eNames` (or `LJM_eAddresses`) call.
+///
+/// ```rust,ignore
+/// let result = Transaction::new()
+///     .write("DAC0", 2.5)
+///     .read("AIN0")
+///     .read_array("AIN0", 4)
+///     .execute(handle)?;
+///
+/// let ain0 = result.value(1);
+/// ```
+///
+/// Results are indexed by the order in which steps were added.
+/// If every step is named, the names are sent as-is, otherwise
+/// they are resolved to addresses using `LJMLibrary::name_to_address`.
+#[derive(Clone, Debug, Default)]
+pub struct Transaction {
+    pub(crate) steps: Vec<Step>,
+}
+
+impl Transaction {
+    pub fn new() -> Self {
+        Transaction::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    pub fn write<T: ToString, V: Into<f64>>(mut self, name: T, value: V) -> Self {
+        self.steps.push(Step::Write(
+            Target::Name(name.to_string()),
+            vec![value.into()],
+        ));
+        self
+    }
+
+    pub fn write_array<T: ToString>(mut self, name: T, values: &[f64]) -> Self {
+        self.steps
+            .push(Step::Write(Target::Name(name.to_string()), values.to_vec()));
+        self
+    }
+
+    pub fn read<T: ToString>(mut self, name: T) -> Self {
+        self.steps.push(Step::Read(Target::Name(name.to_string())));
+        self
+    }
+
+    /// Reads `count` consecutive values starting at the named register.
+    pub fn read_array<T: ToString>(mut self, name: T, count: usize) -> Self {
+        self.steps
+            .push(Step::ReadArray(Target::Name(name.to_string()), count));
+        self
+    }
+
+    pub fn write_addr<V: Into<f64>>(mut self, address: i32, data_type: i32, value: V) -> Self {
+        self.steps.push(Step::Write(
+            Target::Address(address, data_type),
+            vec![value.into()],
+        ));
+        self
+    }
+
+    pub fn write_addr_array(mut self, address: i32, data_type: i32, values: &[f64]) -> Self {
+        self.steps.push(Step::Write(
+            Target::Address(address, data_type),
+            values.to_vec(),
+        ));
+        self
+    }
+
+    pub fn read_addr(mut self, address: i32, data_type: i32) -> Self {
+        self.steps
+            .push(Step::Read(Target::Address(address, data_type)));
+        self
+    }
+
+    pub fn read_addr_array(mut self, address: i32, data_type: i32, count: usize) -> Self {
+        self.steps
+            .push(Step::ReadArray(Target::Address(address, data_type), count));
+        self
+    }
+
+    /// Executes the transaction against the given handle.
+    /// Equivalent to `LJMLibrary::transaction(handle, &self)`.
+    pub fn execute(&self, handle: i32) -> Result<TransactionResult, LJMError> {
+        LJMLibrary::transaction(handle, self)
+    }
+
+    // Returns the (aWrites, aNumValues, aValues) arrays for the transaction.
+    pub(crate) fn compile(&self) -> (Vec<i32>, Vec<i32>, Vec<f64>) {
+        let mut writes = Vec::with_capacity(self.steps.len());
+        let mut num_values = Vec::with_capacity(self.steps.len());
+        let mut values = Vec::new();
+
+        for step in &self.steps {
+            match step {
+                Step::Write(_, to_write) => {
+                    writes.push(LJM_WRITE);
+                    num_values.push(to_write.len() as i32);
+                    values.extend_from_slice(to_write);
+                }
+                Step::Read(_) => {
+                    writes.push(LJM_READ);
+                    num_values.push(1);
+                    values.push(0.0);
+                }
+                Step::ReadArray(_, count) => {
+                    writes.push(LJM_READ);
+                    num_values.push(*count as i32);
+                    values.resize(values.len() + count, 0.0);
+                }
+            }
+        }
+
+        (writes, num_values, values)
+    }
+
+    // Splits the values returned by LJM back into each step.
+    pub(crate) fn results(&self, values: &[f64]) -> TransactionResult {
+        let mut offset = 0;
+
+        let steps = self
+            .steps
+            .iter()
+            .map(|step| match step {
+                Step::Write(_, written) => {
+                    offset += written.len();
+                    StepResult::Written
+                }
+                Step::Read(_) => {
+                    offset += 1;
+                    StepResult::Value(values[offset - 1])
+                }
+                Step::ReadArray(_, count) => {
+                    offset += count;
+                    StepResult::Array(values[offset - count..offset].to_vec())
+                }
+            })
+            .collect();
+
+        TransactionResult { steps }
+    }
+}
+
+impl Step {
+    pub(crate) fn target(&self) -> &Target {
+        match self {
+            Step::Write(target, _) | Step::Read(target) | Step::ReadArray(target, _) => target,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum StepResult {
+    Written,
+    Value(f64),
+    Array(Vec<f64>),
+}
+
+/// The outcome of each step of an executed `Transaction`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TransactionResult {
+    steps: Vec<StepResult>,
+}
+
+impl TransactionResult {
+    pub fn get(&self, step: usize) -> Option<&StepResult> {
+        self.steps.get(step)
+    }
+
+    /// The value of a `read` step, or `None` if the step was not a single read.
+    pub fn value(&self, step: usize) -> Option<f64> {
+        match self.steps.get(step)? {
+            StepResult::Value(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// The values of a `read_array` step, or `None` if the step was not an array read.
+    pub fn array(&self, step: usize) -> Option<&[f64]> {
+        match self.steps.get(step)? {
+            StepResult::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub fn steps(&self) -> &[StepResult] {
+        &self.steps
+    }
+}
+
+impl std::ops::Index<usize> for TransactionResult {
+    type Output = StepResult;
+
+    fn index(&self, step: usize) -> &Self::Output {
+        &self.steps[step]
+    }
+}
@@ -0,0 +1,46 @@
+use std::f64::consts::PI;
+
+/// Built-in periodic waveforms for `LJMLibrary::periodic_stream_out`.
+///
+/// ```rust,ignore
+/// let points = Waveform::samples_for(scan_rate, 100.0);
+/// let sine = Waveform::Sine.generate(points, 1.5, 2.5); // 1V to 4V
+///
+/// LJMLibrary::periodic_stream_out(handle, 0, "DAC0", scan_rate, &sine)?;
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Waveform {
+    Sine,
+    Square,
+    Triangle,
+    Sawtooth,
+}
+
+impl Waveform {
+    /// Generates one period of the waveform over `samples` points,
+    /// swinging `amplitude` either side of `offset`.
+    pub fn generate(&self, samples: usize, amplitude: f64, offset: f64) -> Vec<f64> {
+        (0..samples)
+            .map(|i| {
+                // Phase within the period, in [0, 1)
+                let phase = i as f64 / samples as f64;
+
+                let unit = match self {
+                    Waveform::Sine => (2.0 * PI * phase).sin(),
+                    Waveform::Square if phase < 0.5 => 1.0,
+                    Waveform::Square => -1.0,
+                    Waveform::Triangle => 1.0 - 4.0 * (phase - 0.5).abs(),
+                    Waveform::Sawtooth => 2.0 * phase - 1.0,
+                };
+
+                offset + amplitude * unit
+            })
+            .collect()
+    }
+
+    /// The number of samples in one period of `frequency`
+    /// when streamed out at `scan_rate`.
+    pub fn samples_for(scan_rate: f64, frequency: f64) -> usize {
+        (scan_rate / frequency).round().max(1.0) as usize
+    }
+}
@@ -0,0 +1,75 @@
+use ljmrs::{CalibrationTable, DeviceType, LJMError, StreamConfig};
+
+#[rustfmt::skip]
+const T7_CONSTANTS: [f32; 16] = [
+    // PSlope, NSlope, Center, Offset
+    3.1e-4, -3.2e-4, 33000.0, -10.5,
+    3.1e-5, -3.2e-5, 33100.0, -1.05,
+    3.1e-6, -3.2e-6, 33200.0, -0.105,
+    3.1e-7, -3.2e-7, 33300.0, -0.0105,
+];
+
+#[test]
+fn apply_calibration() {
+    let table = CalibrationTable::from_t7_constants(&T7_CONSTANTS).expect("constants are valid");
+    assert_eq!(table.ranges().len(), 4);
+
+    let ten = table.get(10.0).expect("±10V is calibrated");
+    assert_eq!(ten.center, 33000.0);
+
+    let volts = |range, counts| table.apply(range, counts).expect("range is calibrated");
+    assert_eq!(volts(10.0, 33000), 0.0);
+    assert!((volts(10.0, 34000) - 1000.0 * 3.1e-4).abs() < 1e-6);
+    assert!((volts(10.0, 32000) + 1000.0 * 3.2e-4).abs() < 1e-6);
+    assert!((volts(0.01, 33400) - 100.0 * 3.1e-7).abs() < 1e-9);
+
+    assert_eq!(table.apply(2.4, 33000), None);
+}
+
+#[test]
+fn invalid_calibration() {
+    assert!(matches!(
+        CalibrationTable::from_t7_constants(&T7_CONSTANTS[..12]),
+        Err(LJMError::InvalidCalibration(_))
+    ));
+
+    // Unprogrammed flash
+    let mut constants = T7_CONSTANTS;
+    constants[6] = f32::from_bits(u32::MAX);
+    assert!(matches!(
+        CalibrationTable::from_t7_constants(&constants),
+        Err(LJMError::InvalidCalibration(_))
+    ));
+}
+
+#[test]
+fn binary_stream_devices() {
+    let config = StreamConfig::new(100, 1000.0).channel("AIN0").binary(true);
+    assert!(config.is_binary());
+
+    assert!(config.validate(&DeviceType::T7).is_ok());
+    for device_type in [DeviceType::T4, DeviceType::T8, DeviceType::EMULATED(-4)] {
+        assert!(matches!(
+            config.validate(&device_type),
+            Err(LJMError::InvalidStreamConfig(_))
+        ));
+    }
+}
+
+#[test]
+fn binary_streams_only_ain() {
+    let config = StreamConfig::new(100, 1000.0)
+        .channel("AIN0")
+        .channel("AIN12")
+        .binary(true);
+    assert!(config.validate(&DeviceType::T7).is_ok());
+
+    for channel in ["DIO0_EF_READ_A", "FIO_STATE", "AIN", "0"] {
+        let config = config.clone().channel(channel);
+
+        assert!(matches!(
+            config.validate(&DeviceType::T7),
+            Err(LJMError::InvalidStreamConfig(_))
+        ));
+    }
+}
@@ -0,0 +1,94 @@
+use std::time::Duration;
+
+use ljmrs::{
+    Capture, CaptureLayout, StreamClock, CORE_TIMER_ADDRESS, LJM_DUMMY_VALUE, LJM_SCAN_NOT_READ,
+    STREAM_DATA_CAPTURE_16_ADDRESS,
+};
+
+const DIO0_EF_READ_A: i32 = 3000;
+const DIO1_EF_READ_A: i32 = 3002;
+
+fn layout() -> CaptureLayout {
+    CaptureLayout::new(
+        3,
+        vec![
+            Capture::for_type(0, 1).expect("LJM_UINT32 is captured"),
+            Capture::for_type(2, 2).expect("LJM_INT32 is captured"),
+        ],
+    )
+}
+
+#[test]
+fn capture_scan_list() {
+    assert!(Capture::for_type(1, 3).is_none());
+    assert!(Capture::for_type(1, 0).is_none());
+
+    let layout = layout();
+    assert_eq!(layout.logical_len(), 3);
+    assert_eq!(layout.physical_len(), 5);
+    assert_eq!(
+        layout.scan_list(&[DIO0_EF_READ_A, 0, DIO1_EF_READ_A]),
+        vec![
+            DIO0_EF_READ_A,
+            STREAM_DATA_CAPTURE_16_ADDRESS,
+            0,
+            DIO1_EF_READ_A,
+            STREAM_DATA_CAPTURE_16_ADDRESS,
+        ]
+    );
+}
+
+#[test]
+fn capture_combine() {
+    let layout = layout();
+
+    #[rustfmt::skip]
+    let physical = [
+        0x5678 as f64, 0x1234 as f64, 1.5, 0xFFFE as f64, 0xFFFF as f64,
+        LJM_DUMMY_VALUE, LJM_DUMMY_VALUE, 2.5, 7.0, LJM_SCAN_NOT_READ,
+    ];
+    let mut logical = [0.0; 6];
+    layout.combine(&physical, &mut logical);
+
+    assert_eq!(
+        logical,
+        [
+            0x1234_5678 as f64,
+            1.5,
+            -2.0,
+            LJM_DUMMY_VALUE,
+            2.5,
+            LJM_SCAN_NOT_READ
+        ]
+    );
+}
+
+#[test]
+fn captured_core_timer() {
+    // At 10 Hz, CORE_TIMER advances 4,000,000 ticks per scan. With its upper
+    // 16 bits captured, only wraps of the full 32 bits need resolving.
+    let captures = [Capture {
+        index: 1,
+        signed: false,
+    }];
+    let clock = StreamClock::new(10.0, &[0, CORE_TIMER_ADDRESS]).with_captures(&captures);
+    let raw = |ticks: u64| (ticks % (1 << 32)) as f64;
+
+    // The second scan is late by 40,000 ticks (1 ms), which would be
+    // ambiguous were only the lower 16 bits streamed.
+    let data = [0.0, raw(0), 0.0, raw(4_040_000), 0.0, raw(8_000_000)];
+
+    let timing = clock.advance(&data, true);
+    let measured = timing.measured.expect("CORE_TIMER is streamed");
+
+    assert_eq!(measured[1], Duration::from_millis(101));
+    assert_eq!(measured[2], Duration::from_millis(200));
+
+    // Over the next 1,100 scans, CORE_TIMER wraps.
+    let data: Vec<f64> = (3..1103)
+        .flat_map(|scan| [0.0, raw(4_000_000 * scan)])
+        .collect();
+
+    let timing = clock.advance(&data, true);
+    assert_eq!(timing.elapsed(1099), Duration::from_secs_f64(110.2));
+}
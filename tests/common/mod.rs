@@ -0,0 +1,37 @@
+// Fixtures shared by the integration tests, each of which uses only some.
+#![allow(dead_code)]
+
+use std::time::SystemTime;
+
+use ljmrs::{LJMLibrary, Recording, RecordingHeader, Replay, StreamBlock};
+
+/// A block of `data`, interleaved over `num_channels` channels.
+pub fn block(data: Vec<f64>, num_channels: usize) -> StreamBlock {
+    StreamBlock::new(data, num_channels, 0, 0, None)
+}
+
+/// A recording of `num_scans` scans of AIN0 (counting up from 0)
+/// and AIN1 (counting down from 0), taken at `scan_rate`.
+pub fn recording(scan_rate: f64, num_scans: usize) -> Recording {
+    Recording {
+        header: RecordingHeader {
+            serial_number: 470012345,
+            scan_rate,
+            started_at: SystemTime::UNIX_EPOCH,
+            names: vec!["AIN0".into(), "AIN1".into()],
+            addresses: vec![0, 2],
+        },
+        channels: vec![
+            (0..num_scans).map(|i| i as f64).collect(),
+            (0..num_scans).map(|i| -(i as f64)).collect(),
+        ],
+    }
+}
+
+/// Opens `replay` as a virtual handle, without needing LJM.
+pub fn replay_handle(replay: Replay) -> i32 {
+    // Each test may be the first to initialize the wrapper.
+    let _ = LJMLibrary::init_replay();
+
+    LJMLibrary::open_replay(replay).unwrap()
+}
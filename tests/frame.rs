@@ -0,0 +1,51 @@
+use ljmrs::{LJMLibrary, StreamFrame};
+
+mod common;
+
+fn frame() -> StreamFrame {
+    let block = common::block(vec![1.0, 10.0, 2.0, 20.0, 3.0, 30.0], 2);
+
+    StreamFrame::new(block, vec!["AIN0".into(), "AIN1".into()], vec![0, 2])
+}
+
+#[test]
+fn channel_views() {
+    let frame = frame();
+
+    let ain0 = frame.channel("AIN0").expect("AIN0 is streamed");
+    assert_eq!(ain0.to_vec(), vec![1.0, 2.0, 3.0]);
+    assert_eq!(ain0.len(), 3);
+    assert_eq!(ain0.get(2), Some(3.0));
+    assert_eq!(ain0.get(3), None);
+
+    let ain1 = frame.channel_by_address(2).expect("address 2 is streamed");
+    assert_eq!(ain1.name, "AIN1");
+    assert_eq!(ain1.iter().sum::<f64>(), 60.0);
+
+    assert!(frame.channel("AIN2").is_none());
+    assert_eq!(frame.to_map()["AIN1"], vec![10.0, 20.0, 30.0]);
+}
+
+#[test]
+fn scan_views() {
+    let frame = frame();
+    let scans: Vec<&[f64]> = frame.scans().collect();
+
+    assert_eq!(frame.num_scans(), 3);
+    assert_eq!(scans, vec![&[1.0, 10.0][..], &[2.0, 20.0], &[3.0, 30.0]]);
+}
+
+#[test]
+fn stream_out_channel() {
+    let name = LJMLibrary::stream_out_name(4801).expect("4801 is STREAM_OUT1");
+    assert_eq!(name, "STREAM_OUT1");
+    assert_eq!(LJMLibrary::stream_out_name(4804), None);
+
+    let frame = StreamFrame::new(frame().block, vec!["AIN0".into(), name], vec![0, 4801]);
+
+    let stream_out = frame
+        .channel("STREAM_OUT1")
+        .expect("STREAM_OUT1 is streamed");
+    assert_eq!(stream_out.to_vec(), vec![10.0, 20.0, 30.0]);
+    assert_eq!(frame.channel_by_address(4801).unwrap().name, "STREAM_OUT1");
+}
@@ -0,0 +1,34 @@
+use ljmrs::{DeviceType, DiscoveredRegister};
+
+#[test]
+fn device_type_round_trip() {
+    for device_type in [DeviceType::T4, DeviceType::T7, DeviceType::T8] {
+        let value = i32::from(&device_type);
+        assert_eq!(i32::from(&DeviceType::from(value)), value);
+    }
+}
+
+#[test]
+fn decode_discovered_registers() {
+    let firmware = DiscoveredRegister {
+        name: "FIRMWARE_VERSION".to_string(),
+        address: 60004,
+        data_type: 3,
+        bytes: 1.0299_f32.to_be_bytes().to_vec(),
+    };
+
+    assert_eq!(firmware.as_f64(), Some(1.0299_f32 as f64));
+
+    let mut name = b"RackT7".to_vec();
+    name.resize(50, 0);
+
+    let device_name = DiscoveredRegister {
+        name: "DEVICE_NAME_DEFAULT".to_string(),
+        address: 60500,
+        data_type: 98,
+        bytes: name,
+    };
+
+    assert_eq!(device_name.as_string().as_deref(), Some("RackT7"));
+    assert_eq!(device_name.as_f64(), None);
+}
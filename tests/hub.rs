@@ -0,0 +1,106 @@
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use ljmrs::{LJMLibrary, Replay, SlowConsumerPolicy, StreamBlock, StreamHub};
+
+mod common;
+
+// A block of a single scan of two channels.
+fn block(value: f64) -> StreamBlock {
+    common::block(vec![value; 2], 2)
+}
+
+fn value(block: Option<Arc<StreamBlock>>) -> f64 {
+    block.expect("a block is queued").data[0]
+}
+
+#[test]
+fn broadcasts_to_subscribers() {
+    let hub = StreamHub::new(1);
+    let logger = hub.subscribe(8, SlowConsumerPolicy::Block);
+    let plot = hub.subscribe(8, SlowConsumerPolicy::DropOldest);
+    assert_eq!(hub.subscribers(), 2);
+
+    hub.publish(block(1.0));
+    hub.publish(block(2.0));
+
+    let first = logger.recv().unwrap();
+    assert!(Arc::ptr_eq(&first, &plot.recv().unwrap()));
+    assert_eq!(value(logger.try_recv()), 2.0);
+    assert_eq!(plot.stats().lag, 1);
+
+    drop(logger);
+    assert_eq!(hub.subscribers(), 1);
+
+    hub.stop().unwrap();
+    assert!(!plot.is_connected());
+    // Queued blocks are still received once the hub has stopped.
+    assert_eq!(value(plot.recv()), 2.0);
+    assert!(plot.recv().is_none());
+}
+
+#[test]
+fn drop_oldest_and_disconnect() {
+    let hub = StreamHub::new(1);
+    let plot = hub.subscribe(2, SlowConsumerPolicy::DropOldest);
+    let alarm = hub.subscribe(2, SlowConsumerPolicy::Disconnect);
+
+    for i in 0..4 {
+        hub.publish(block(i as f64));
+    }
+
+    let stats = plot.stats();
+    assert_eq!((stats.published, stats.dropped, stats.lag), (4, 2, 2));
+    assert_eq!(value(plot.recv()), 2.0);
+    assert_eq!(value(plot.recv()), 3.0);
+    assert_eq!(plot.stats().received, 2);
+
+    assert!(!alarm.is_connected());
+    assert_eq!(hub.subscribers(), 1);
+    assert_eq!(alarm.stats().dropped, 1);
+    assert_eq!(value(alarm.recv()), 0.0);
+    assert_eq!(value(alarm.recv()), 1.0);
+    assert!(alarm.recv().is_none());
+}
+
+#[test]
+fn block_waits_for_subscriber() {
+    let hub = Arc::new(StreamHub::new(1));
+    let logger = hub.subscribe(1, SlowConsumerPolicy::Block);
+
+    hub.publish(block(0.0));
+
+    let publisher = {
+        let hub = hub.clone();
+        thread::spawn(move || hub.publish(block(1.0)))
+    };
+
+    thread::sleep(Duration::from_millis(50));
+    assert!(!publisher.is_finished());
+    assert_eq!(logger.stats().max_lag, 1);
+
+    assert_eq!(value(logger.recv()), 0.0);
+    publisher.join().unwrap();
+    assert_eq!(value(logger.recv_timeout(Duration::from_secs(1))), 1.0);
+    assert!(logger.recv_timeout(Duration::from_millis(10)).is_none());
+}
+
+#[test]
+fn stop_does_not_wait_for_read() {
+    // One scan per second, so the first read waits a second.
+    let handle = common::replay_handle(Replay::new(common::recording(1.0, 4)));
+    LJMLibrary::stream_start(handle, 1, 1.0, vec![0]).unwrap();
+
+    let hub = StreamHub::start(handle).unwrap();
+    let subscription = hub.subscribe(4, SlowConsumerPolicy::Block);
+    thread::sleep(Duration::from_millis(100));
+
+    let now = Instant::now();
+    hub.stop().unwrap();
+
+    assert!(now.elapsed() < Duration::from_millis(500));
+    assert!(subscription.recv().is_none());
+
+    LJMLibrary::close_replay(handle).unwrap();
+}
@@ -5,7 +5,7 @@ fn assert_error(error: LJMError, error_code: i32) {
     assert!(matches!(
         error,
         LJMError::ErrorCode(
-           i, _j
+           i, _j, _
         ) if i == LJMErrorCode::from(error_code)
     ));
 }
@@ -95,3 +95,18 @@ fn uaw2() {
 
     assert!(true);
 }
+
+#[test]
+fn oversized_string() {
+    let _ = unsafe { LJMLibrary::init(None) };
+
+    // Forge a fake handle
+    let handle: i32 = -1;
+
+    // One byte longer than LJM_STRING_MAX_SIZE
+    let name = "A".repeat(ljmrs::LJM_STRING_MAX_SIZE + 1);
+
+    let result = LJMLibrary::write_name_string(handle, "DEVICE_NAME_DEFAULT", name);
+
+    assert!(matches!(result, Err(LJMError::InvalidString(_))));
+}
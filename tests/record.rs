@@ -0,0 +1,120 @@
+use std::io::Cursor;
+use std::time::{Duration, SystemTime};
+
+use ljmrs::{Recording, RecordingFormat, RecordingHeader, StreamBlock, StreamRecorder};
+
+mod common;
+
+fn header() -> RecordingHeader {
+    RecordingHeader {
+        serial_number: 470012345,
+        scan_rate: 1000.0,
+        started_at: SystemTime::UNIX_EPOCH + Duration::from_nanos(1_700_000_000_123_456_789),
+        names: vec!["AIN0".into(), "AIN1".into()],
+        addresses: vec![0, 2],
+    }
+}
+
+// A block of scans of the two channels of `header`.
+fn block(data: Vec<f64>) -> StreamBlock {
+    common::block(data, 2)
+}
+
+fn roundtrip(format: RecordingFormat) -> Recording {
+    let mut recorder = StreamRecorder::new(Vec::new(), format, header()).unwrap();
+    recorder
+        .write_block(&block(vec![0.5, 1.25, -0.1, 2.5]))
+        .unwrap();
+    recorder.write_block(&block(vec![1e-9, -9999.0])).unwrap();
+    assert_eq!(recorder.scans(), 3);
+
+    let bytes = recorder.finish().unwrap();
+    match format {
+        RecordingFormat::Csv => Recording::read_csv(Cursor::new(bytes)).unwrap(),
+        RecordingFormat::Binary => Recording::read_binary(Cursor::new(bytes)).unwrap(),
+    }
+}
+
+#[test]
+fn csv_roundtrip() {
+    let recording = roundtrip(RecordingFormat::Csv);
+
+    assert_eq!(recording.header, header());
+    assert_eq!(recording.num_scans(), 3);
+    assert_eq!(recording.channel("AIN0"), Some(&[0.5, -0.1, 1e-9][..]));
+    assert_eq!(
+        recording.channel_by_address(2),
+        Some(&[1.25, 2.5, -9999.0][..])
+    );
+}
+
+#[test]
+fn binary_roundtrip() {
+    let recording = roundtrip(RecordingFormat::Binary);
+
+    assert_eq!(recording.header, header());
+    assert_eq!(
+        recording.channels,
+        vec![vec![0.5, -0.1, 1e-9], vec![1.25, 2.5, -9999.0]]
+    );
+}
+
+#[test]
+fn csv_layout() {
+    let mut recorder = StreamRecorder::new(Vec::new(), RecordingFormat::Csv, header()).unwrap();
+    recorder
+        .write_block(&block(vec![1.0, 2.0, 3.0, 4.0]))
+        .unwrap();
+
+    let csv = String::from_utf8(recorder.finish().unwrap()).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+
+    assert_eq!(lines[3], "# addresses=0,2");
+    assert_eq!(&lines[4..], ["time,AIN0,AIN1", "0,1,2", "0.001,3,4"]);
+}
+
+#[test]
+fn truncated_binary() {
+    let mut recorder = StreamRecorder::new(Vec::new(), RecordingFormat::Binary, header()).unwrap();
+    recorder.write_block(&block(vec![1.0, 2.0])).unwrap();
+
+    let mut bytes = recorder.finish().unwrap();
+    bytes.truncate(bytes.len() - 8);
+    bytes.extend_from_slice(&[0; 4]);
+
+    assert!(Recording::read_binary(Cursor::new(bytes)).is_err());
+}
+
+#[test]
+fn binary_channel_count_is_bounded() {
+    let recorder = StreamRecorder::new(Vec::new(), RecordingFormat::Binary, header()).unwrap();
+    let mut bytes = recorder.finish().unwrap();
+
+    // num_channels follows the magic, version, serial number, scan rate and start time.
+    bytes[26..30].copy_from_slice(&u32::MAX.to_le_bytes());
+
+    assert!(Recording::read_binary(Cursor::new(bytes)).is_err());
+}
+
+#[test]
+fn csv_quotes_names() {
+    let header = RecordingHeader {
+        names: vec!["AIN0,raw".into(), "say \"hi\"".into(), "two\nlines".into()],
+        addresses: vec![0, 2, 4],
+        ..header()
+    };
+
+    let mut recorder =
+        StreamRecorder::new(Vec::new(), RecordingFormat::Csv, header.clone()).unwrap();
+    recorder
+        .write_block(&common::block(vec![1.0, 2.0, 3.0], 3))
+        .unwrap();
+
+    let csv = recorder.finish().unwrap();
+    assert!(String::from_utf8_lossy(&csv)
+        .contains("time,\"AIN0,raw\",\"say \"\"hi\"\"\",\"two\nlines\"\n"));
+
+    let recording = Recording::read_csv(Cursor::new(csv)).unwrap();
+    assert_eq!(recording.header, header);
+    assert_eq!(recording.channels, vec![vec![1.0], vec![2.0], vec![3.0]]);
+}
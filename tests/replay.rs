@@ -0,0 +1,83 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
+use ljmrs::{LJMError, LJMLibrary, Replay};
+
+mod common;
+
+#[test]
+fn replays_scan_list() {
+    let mut replay = Replay::new(common::recording(1000.0, 6)).with_speed(f64::INFINITY);
+    assert_eq!(replay.start(&[2, 0]).unwrap(), 1000.0);
+
+    let mut buffer = [0.0; 6];
+    replay.read(&mut buffer).unwrap();
+    assert_eq!(buffer, [0.0, 0.0, -1.0, 1.0, -2.0, 2.0]);
+
+    replay.read(&mut buffer).unwrap();
+    assert_eq!(buffer, [-3.0, 3.0, -4.0, 4.0, -5.0, 5.0]);
+    assert_eq!(replay.position(), 6);
+
+    assert!(matches!(
+        replay.read(&mut buffer),
+        Err(LJMError::EndOfRecording)
+    ));
+}
+
+#[test]
+fn replay_loops() {
+    let mut replay = Replay::new(common::recording(1000.0, 3))
+        .with_speed(f64::INFINITY)
+        .with_looping(true);
+    replay.start(&[0]).unwrap();
+
+    let mut buffer = [0.0; 5];
+    replay.read(&mut buffer).unwrap();
+    assert_eq!(buffer, [0.0, 1.0, 2.0, 0.0, 1.0]);
+}
+
+#[test]
+fn replay_requires_recorded_channels() {
+    let mut replay = Replay::new(common::recording(1000.0, 3));
+
+    assert!(matches!(
+        replay.start(&[0, 4]),
+        Err(LJMError::InvalidRecording(_))
+    ));
+    assert!(matches!(
+        replay.read(&mut [0.0; 2]),
+        Err(LJMError::StreamNotStarted)
+    ));
+}
+
+#[test]
+fn replay_is_paced() {
+    // 100 scans at 1 kHz, replayed 5x faster, take 20 ms.
+    let mut replay = Replay::new(common::recording(1000.0, 100)).with_speed(5.0);
+    replay.start(&[0]).unwrap();
+
+    let now = Instant::now();
+    let (_, ljm_scan_backlog) = replay.read(&mut [0.0; 100]).unwrap();
+
+    assert!(now.elapsed() >= Duration::from_millis(20));
+    assert!(ljm_scan_backlog < 100);
+}
+
+#[test]
+fn waiting_read_does_not_block_other_handles() {
+    // 10 scans at 10 Hz take a second to be served.
+    let slow = common::replay_handle(Replay::new(common::recording(10.0, 20)));
+    LJMLibrary::stream_start(slow, 10, 10.0, vec![0]).unwrap();
+    let reader = thread::spawn(move || LJMLibrary::stream_read(slow));
+    thread::sleep(Duration::from_millis(100));
+
+    let now = Instant::now();
+    let fast = common::replay_handle(Replay::new(common::recording(1000.0, 4)));
+    LJMLibrary::stream_start(fast, 2, 1000.0, vec![0]).unwrap();
+    LJMLibrary::stream_stop(fast).unwrap();
+    LJMLibrary::close_replay(fast).unwrap();
+    assert!(now.elapsed() < Duration::from_millis(500));
+
+    assert_eq!(reader.join().unwrap().unwrap().len(), 10);
+    LJMLibrary::close_replay(slow).unwrap();
+}
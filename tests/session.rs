@@ -0,0 +1,59 @@
+use std::panic;
+
+use ljmrs::{LJMError, LJMLibrary, Replay, StreamSession};
+
+mod common;
+
+// Streams from a replay of `num_scans` scans, served as fast as they are read.
+fn replay_handle(num_scans: usize) -> i32 {
+    common::replay_handle(
+        Replay::new(common::recording(1000.0, num_scans)).with_speed(f64::INFINITY),
+    )
+}
+
+#[test]
+fn stops_on_drop() {
+    let handle = replay_handle(4);
+
+    {
+        let session = StreamSession::start(handle, 2, 1000.0, vec![0]).unwrap();
+        assert_eq!(session.scan_rate(), 1000.0);
+        assert_eq!(session.read().unwrap(), vec![0.0, 1.0]);
+        assert!(LJMLibrary::is_stream_active(handle));
+    }
+
+    assert!(!LJMLibrary::is_stream_active(handle));
+    assert!(matches!(
+        LJMLibrary::stream_read(handle),
+        Err(LJMError::StreamNotStarted)
+    ));
+}
+
+#[test]
+fn stops_on_panic() {
+    let handle = replay_handle(4);
+
+    let result = panic::catch_unwind(|| {
+        let session = StreamSession::start(handle, 2, 1000.0, vec![0]).unwrap();
+        session.read().unwrap();
+        panic!("acquisition failed");
+    });
+
+    assert!(result.is_err());
+    assert!(!LJMLibrary::is_stream_active(handle));
+}
+
+#[test]
+fn stop_returns_stream() {
+    let handle = replay_handle(4);
+
+    let session = StreamSession::start(handle, 2, 1000.0, vec![0]).unwrap();
+    let stream = session.stop().unwrap();
+
+    assert!(stream.is_some());
+    assert!(!LJMLibrary::is_stream_active(handle));
+
+    // The handle may be streamed from again.
+    let session = StreamSession::start(handle, 4, 1000.0, vec![0]).unwrap();
+    assert_eq!(session.read().unwrap(), vec![0.0, 1.0, 2.0, 3.0]);
+}
@@ -60,3 +60,44 @@ fn standard_open_read() {
 
     assert_eq!(read_value, 0f64);
 }
+
+#[test]
+fn batched_read() {
+    let _ = unsafe { LJMLibrary::init(None) };
+
+    let open_call = LJMLibrary::open_jack(
+        ljmrs::DeviceType::ANY,
+        ljmrs::ConnectionType::ANY,
+        "-2".to_string(),
+    )
+    .expect("Could not open DEMO LabJack");
+
+    let read_values = LJMLibrary::read_names(open_call, &["TEST_INT32", "AIN0", "AIN1"])
+        .expect("Expected Values");
+
+    assert_eq!(read_values.len(), 3);
+    assert_eq!(read_values[0], 0f64);
+}
+
+#[test]
+fn transaction() {
+    let _ = unsafe { LJMLibrary::init(None) };
+
+    let open_call = LJMLibrary::open_jack(
+        ljmrs::DeviceType::ANY,
+        ljmrs::ConnectionType::ANY,
+        "-2".to_string(),
+    )
+    .expect("Could not open DEMO LabJack");
+
+    let result = ljmrs::Transaction::new()
+        .write("DAC0", 2.5)
+        .read("TEST_INT32")
+        .read_array("AIN0", 4)
+        .execute(open_call)
+        .expect("Expected Values");
+
+    assert_eq!(result[0], ljmrs::StepResult::Written);
+    assert_eq!(result.value(1), Some(0f64));
+    assert_eq!(result.array(2).map(|values| values.len()), Some(4));
+}
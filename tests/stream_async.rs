@@ -0,0 +1,89 @@
+#![cfg(feature = "tokio")]
+
+use std::future::poll_fn;
+use std::pin::Pin;
+use std::time::Duration;
+
+use futures_core::Stream;
+use ljmrs::{AsyncStream, LJMError, LJMLibrary, Replay};
+
+mod common;
+
+// Streams from a replay of `num_scans` scans, served as fast as they are read.
+fn replay_handle(num_scans: usize) -> i32 {
+    common::replay_handle(
+        Replay::new(common::recording(1000.0, num_scans)).with_speed(f64::INFINITY),
+    )
+}
+
+async fn next(stream: &mut AsyncStream) -> Option<Result<Vec<f64>, LJMError>> {
+    poll_fn(|cx| Pin::new(&mut *stream).poll_next(cx))
+        .await
+        .map(|block| block.map(|block| block.data))
+}
+
+#[tokio::test]
+async fn buffers_up_to_capacity() {
+    let handle = replay_handle(100);
+    LJMLibrary::stream_start(handle, 1, 1000.0, vec![0]).unwrap();
+
+    let mut stream = LJMLibrary::stream_async(handle, 2).unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    // Two blocks are buffered, and the reader waits to send a third.
+    let replay = LJMLibrary::close_replay(handle).unwrap().unwrap();
+    assert_eq!(replay.position(), 3);
+
+    for value in 0..3 {
+        assert_eq!(
+            next(&mut stream).await.unwrap().unwrap(),
+            vec![value as f64]
+        );
+    }
+}
+
+#[tokio::test]
+async fn ends_after_error() {
+    let handle = replay_handle(2);
+    LJMLibrary::stream_start(handle, 1, 1000.0, vec![0]).unwrap();
+
+    let mut stream = LJMLibrary::stream_async(handle, 4).unwrap();
+
+    assert_eq!(next(&mut stream).await.unwrap().unwrap(), vec![0.0]);
+    assert_eq!(next(&mut stream).await.unwrap().unwrap(), vec![1.0]);
+    assert!(matches!(
+        next(&mut stream).await,
+        Some(Err(LJMError::EndOfRecording))
+    ));
+    assert!(next(&mut stream).await.is_none());
+
+    LJMLibrary::close_replay(handle).unwrap();
+}
+
+#[tokio::test]
+async fn ends_once_stopped() {
+    let handle = replay_handle(100);
+    LJMLibrary::stream_start(handle, 1, 1000.0, vec![0]).unwrap();
+
+    let mut stream = LJMLibrary::stream_async(handle, 1).unwrap();
+    LJMLibrary::stream_stop(handle).unwrap();
+
+    // Blocks read before the stream stopped are still yielded.
+    let mut result = next(&mut stream).await;
+    while let Some(Ok(_)) = result {
+        result = next(&mut stream).await;
+    }
+
+    assert!(matches!(result, Some(Err(LJMError::StreamNotStarted))));
+    assert!(next(&mut stream).await.is_none());
+}
+
+#[tokio::test]
+async fn requires_a_stream() {
+    let handle = replay_handle(1);
+
+    assert!(matches!(
+        LJMLibrary::stream_async(handle, 1),
+        Err(LJMError::StreamNotStarted)
+    ));
+}
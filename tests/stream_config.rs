@@ -0,0 +1,133 @@
+use ljmrs::{DeviceType, LJMError, StreamClockSource, StreamConfig, StreamTrigger, TriggerEdge};
+
+fn config() -> StreamConfig {
+    StreamConfig::new(100, 1000.0)
+        .channel("AIN0")
+        .channel("AIN2")
+}
+
+fn rejects(config: StreamConfig, device_type: DeviceType) -> bool {
+    matches!(
+        config.validate(&device_type),
+        Err(LJMError::InvalidStreamConfig(_))
+    )
+}
+
+#[test]
+fn registers() {
+    let config = config()
+        .clock_source(StreamClockSource::External)
+        .buffer_size_bytes(16384)
+        .range(2, 1.0)
+        .negative_channel(2, 3);
+
+    assert_eq!(
+        config.registers(),
+        vec![
+            ("STREAM_TRIGGER_INDEX".to_string(), 0.0),
+            ("STREAM_CLOCK_SOURCE".to_string(), 2.0),
+            ("STREAM_SETTLING_US".to_string(), 0.0),
+            ("STREAM_RESOLUTION_INDEX".to_string(), 0.0),
+            ("STREAM_EXTERNAL_CLOCK_DIVISOR".to_string(), 1.0),
+            ("STREAM_BUFFER_SIZE_BYTES".to_string(), 16384.0),
+            ("AIN2_RANGE".to_string(), 1.0),
+            ("AIN2_NEGATIVE_CH".to_string(), 3.0),
+        ]
+    );
+    assert!(config.validate(&DeviceType::T7).is_ok());
+}
+
+#[test]
+fn validates_per_device() {
+    // Differential pairs are only supported by the T7.
+    assert!(config()
+        .negative_channel(0, 1)
+        .validate(&DeviceType::T7)
+        .is_ok());
+    assert!(rejects(config().negative_channel(1, 2), DeviceType::T7));
+    assert!(rejects(config().negative_channel(0, 1), DeviceType::T8));
+
+    // Ranges differ between devices, and are fixed on the T4.
+    assert!(config().range(0, 9.6).validate(&DeviceType::T8).is_ok());
+    assert!(rejects(config().range(0, 9.6), DeviceType::T7));
+    assert!(rejects(config().range(0, 10.0), DeviceType::T4));
+
+    assert!(config()
+        .resolution_index(8)
+        .validate(&DeviceType::T7)
+        .is_ok());
+    assert!(rejects(config().resolution_index(8), DeviceType::T4));
+    assert!(rejects(config().range(14, 10.0), DeviceType::T7));
+
+    // Triggers must be on a DIO supporting Conditional Reset.
+    assert!(config()
+        .trigger_index(2000)
+        .validate(&DeviceType::T7)
+        .is_ok());
+    assert!(rejects(config().trigger_index(2000), DeviceType::T4));
+    assert!(rejects(config().trigger_index(5), DeviceType::T7));
+
+    // External clocks are taken from CIO3, which the T8 lacks.
+    let clocked = config().clock_source(StreamClockSource::External);
+    assert!(clocked.clone().validate(&DeviceType::T4).is_ok());
+    assert!(rejects(clocked, DeviceType::T8));
+    assert!(rejects(
+        config().trigger(StreamTrigger::new(4, TriggerEdge::Rising)),
+        DeviceType::T7
+    ));
+}
+
+#[test]
+fn validates_generally() {
+    assert!(rejects(StreamConfig::new(100, 1000.0), DeviceType::T7));
+    assert!(rejects(config().settling_us(5000.0), DeviceType::T7));
+    assert!(rejects(config().buffer_size_bytes(1000), DeviceType::T7));
+    assert!(rejects(config(), DeviceType::DIGIT));
+    assert!(config().validate(&DeviceType::EMULATED(-2)).is_ok());
+}
+
+#[test]
+fn trigger() {
+    let config = config().trigger(StreamTrigger::new(0, TriggerEdge::Falling));
+    let registers = config.registers();
+
+    assert!(config.is_triggered());
+    assert_eq!(
+        &registers[..5],
+        &[
+            ("DIO0_EF_ENABLE".to_string(), 0.0),
+            ("DIO0_EF_INDEX".to_string(), 12.0),
+            ("DIO0_EF_CONFIG_A".to_string(), 0.0),
+            ("DIO0_EF_ENABLE".to_string(), 1.0),
+            ("STREAM_TRIGGER_INDEX".to_string(), 2000.0),
+        ]
+    );
+
+    assert!(config.validate(&DeviceType::T7).is_ok());
+    assert!(!self::config().is_triggered());
+}
+
+#[test]
+fn library_configs() {
+    let triggered = config().trigger(StreamTrigger::new(0, TriggerEdge::Falling));
+    let clocked = config().clock_source(StreamClockSource::External);
+    // The timeout is written after the mode, so is restored before it.
+    let expected = vec![
+        ("LJM_STREAM_RECEIVE_TIMEOUT_MODE", 2.0),
+        ("LJM_STREAM_RECEIVE_TIMEOUT_MS", 0.0),
+    ];
+
+    assert_eq!(triggered.library_configs(), expected);
+    assert_eq!(clocked.library_configs(), expected);
+    assert!(config().library_configs().is_empty());
+}
+
+#[test]
+fn registers_default() {
+    let registers = config().registers();
+
+    assert!(registers.contains(&("STREAM_BUFFER_SIZE_BYTES".to_string(), 0.0)));
+    assert!(!registers
+        .iter()
+        .any(|(register, _)| register == "STREAM_EXTERNAL_CLOCK_DIVISOR"));
+}
@@ -0,0 +1,44 @@
+use ljmrs::AperiodicQueue;
+
+#[test]
+fn queue_limits_writes() {
+    let mut queue = AperiodicQueue::new(512);
+    // Before the first write, a whole chunk may be written.
+    assert_eq!(queue.limit(), 512);
+    assert_eq!(queue.remaining(), None);
+
+    queue.written(1000);
+    assert_eq!(queue.limit(), 512);
+
+    queue.written(100);
+    assert_eq!(queue.limit(), 100);
+
+    queue.written(0);
+    assert_eq!(queue.limit(), 0);
+
+    // Negative statuses are treated as a full queue.
+    queue.written(-4);
+    assert_eq!(queue.limit(), 0);
+
+    assert_eq!(AperiodicQueue::new(0).limit(), 1);
+}
+
+#[test]
+fn queue_consumed() {
+    let mut queue = AperiodicQueue::new(512);
+
+    // Scans consumed before the space is known are not counted.
+    queue.consumed(100);
+    assert_eq!(queue.remaining(), None);
+
+    queue.written(0);
+    queue.consumed(200);
+    assert_eq!(queue.remaining(), Some(200));
+    assert_eq!(queue.limit(), 200);
+
+    queue.consumed(1000);
+    assert_eq!(queue.limit(), 512);
+
+    queue.consumed(usize::MAX);
+    assert_eq!(queue.remaining(), Some(i32::MAX));
+}
@@ -0,0 +1,63 @@
+use ljmrs::{DeviceType, LJMError, PwmClock, StreamClockSource, StreamConfig, SyncedStreamGroup};
+
+#[test]
+fn pwm_clock() {
+    let core_frequency = PwmClock::core_frequency(&DeviceType::T7).unwrap();
+
+    // 80 MHz / 3 kHz is not a whole number of ticks.
+    let clock = PwmClock::new(0, 3000.0);
+    assert_eq!(
+        clock.actual_frequency(core_frequency),
+        80_000_000.0 / 26667.0
+    );
+
+    let registers = clock.registers(core_frequency);
+    assert!(registers.contains(&("DIO_EF_CLOCK0_ROLL_VALUE".to_string(), 26667.0)));
+    assert!(registers.contains(&("DIO0_EF_CONFIG_A".to_string(), 13333.0)));
+    assert_eq!(registers.last(), Some(&("DIO0_EF_ENABLE".to_string(), 1.0)));
+
+    assert!(PwmClock::core_frequency(&DeviceType::DIGIT).is_none());
+}
+
+#[test]
+fn pwm_clock_line_is_validated() {
+    assert!(PwmClock::new(0, 1000.0).validate(&DeviceType::T7).is_ok());
+    assert!(PwmClock::new(6, 1000.0).validate(&DeviceType::T4).is_ok());
+
+    // DIO1 has no PWM output on the T7, nor DIO0 on the T4.
+    for (dio, device_type) in [(1, DeviceType::T7), (0, DeviceType::T4)] {
+        assert!(matches!(
+            PwmClock::new(dio, 1000.0).validate(&device_type),
+            Err(LJMError::InvalidStreamConfig(_))
+        ));
+    }
+}
+
+#[test]
+fn external_clock_config() {
+    let config = StreamConfig::new(100, 1000.0)
+        .channel("AIN0")
+        .clock_source(StreamClockSource::External)
+        .external_clock_divisor(4);
+
+    assert!(config.is_externally_clocked());
+    assert!(config
+        .registers()
+        .contains(&("STREAM_EXTERNAL_CLOCK_DIVISOR".to_string(), 4.0)));
+    assert!(config.validate(&DeviceType::T7).is_ok());
+
+    let config = config.external_clock_divisor(0);
+    assert!(config.validate(&DeviceType::T7).is_err());
+}
+
+#[test]
+fn clock_frequency_is_validated() {
+    for frequency in [0.0, -1000.0, f64::NAN, f64::INFINITY] {
+        let mut group = SyncedStreamGroup::new(1, PwmClock::new(0, frequency), 100);
+
+        assert!(matches!(
+            group.start(),
+            Err(LJMError::InvalidStreamConfig(_))
+        ));
+    }
+}
@@ -0,0 +1,106 @@
+use std::thread;
+use std::time::Duration;
+
+use ljmrs::{StreamClock, CORE_TIMER_ADDRESS, LJM_DUMMY_VALUE, SYSTEM_TIMER_20HZ_ADDRESS};
+
+#[test]
+fn nominal_timing() {
+    let clock = StreamClock::new(100.0, &[0, 2]);
+
+    let first = clock.advance(&[0.0; 8], true);
+    assert_eq!(first.first_scan, 0);
+    assert!(first.measured.is_none());
+    assert_eq!(first.elapsed(3), Duration::from_millis(30));
+
+    let second = clock.advance(&[0.0; 8], true);
+    assert_eq!(second.first_scan, 4);
+    assert_eq!(second.elapsed(0), Duration::from_millis(40));
+    assert_eq!(
+        second.timestamp(1),
+        second.started_at + Duration::from_millis(50)
+    );
+    assert_eq!(clock.scans(), 8);
+}
+
+#[test]
+fn core_timer_wraps() {
+    // At 1 kHz, CORE_TIMER advances 40,000 ticks per scan, wrapping its lower 16 bits.
+    let clock = StreamClock::new(1000.0, &[0, CORE_TIMER_ADDRESS]);
+    let raw = |ticks: u64| (ticks % 65536) as f64;
+
+    // The third scan is late by 1,000 ticks (25 us).
+    let data = [
+        0.0,
+        raw(0),
+        0.0,
+        raw(40_000),
+        0.0,
+        raw(81_000),
+        0.0,
+        LJM_DUMMY_VALUE,
+    ];
+
+    let timing = clock.advance(&data, true);
+    let measured = timing.measured.as_ref().expect("CORE_TIMER is streamed");
+
+    assert_eq!(measured[1], Duration::from_millis(1));
+    assert_eq!(measured[2], Duration::from_micros(2025));
+    // Dummy readings fall back to the nominal time.
+    assert_eq!(measured[3], Duration::from_millis(3));
+
+    let next = clock.advance(&[0.0, raw(160_000)], true);
+    assert_eq!(next.elapsed(0), Duration::from_millis(4));
+}
+
+#[test]
+fn system_timer() {
+    let clock = StreamClock::new(10.0, &[SYSTEM_TIMER_20HZ_ADDRESS]);
+
+    let timing = clock.advance(&[100.0, 102.0, 105.0], true);
+    let measured = timing.measured.expect("SYSTEM_TIMER_20HZ is streamed");
+
+    assert_eq!(
+        measured,
+        vec![
+            Duration::ZERO,
+            Duration::from_millis(100),
+            Duration::from_millis(250)
+        ]
+    );
+
+    // Measured times are only collected on request.
+    assert!(clock.advance(&[107.0], false).measured.is_none());
+}
+
+#[test]
+fn system_timer_corrects_drift() {
+    // At 100 Hz, SYSTEM_TIMER_20HZ should tick every 5 scans, but
+    // ticks over at scans 2 and 8, 60 ms apart by the nominal times.
+    let clock = StreamClock::new(100.0, &[SYSTEM_TIMER_20HZ_ADDRESS]);
+    let data = [
+        100.0, 100.0, 101.0, 101.0, 101.0, 101.0, 101.0, 101.0, 102.0, 102.0,
+    ];
+
+    let measured = clock.advance(&data, true).measured.unwrap();
+    let millis: Vec<u128> = measured.iter().map(|elapsed| elapsed.as_millis()).collect();
+
+    // Rather than sharing the time of their tick, scans keep their nominal
+    // spacing, corrected by 10 ms once the timer shows they have drifted.
+    assert_eq!(millis, vec![0, 10, 20, 30, 40, 50, 60, 70, 70, 80]);
+}
+
+#[test]
+fn rebase_on_trigger() {
+    let clock = StreamClock::new(100.0, &[0]);
+    let registered = clock.started_at();
+    thread::sleep(Duration::from_millis(50));
+
+    // The trigger fired 10 scans (100 ms) ago, before the clock was made.
+    clock.rebase(10);
+    let timing = clock.advance(&[0.0; 10], true);
+
+    assert!(timing.started_at < registered);
+    assert_eq!(timing.started_at, clock.started_at());
+    assert!(timing.started.elapsed() >= Duration::from_millis(100));
+    assert_eq!(timing.elapsed(0), Duration::ZERO);
+}
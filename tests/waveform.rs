@@ -0,0 +1,57 @@
+use ljmrs::{LJMError, LJMLibrary, Waveform};
+
+fn assert_close(a: f64, b: f64) {
+    assert!((a - b).abs() < 1e-9, "{} != {}", a, b);
+}
+
+#[test]
+fn waveform_bounds() {
+    for waveform in [
+        Waveform::Sine,
+        Waveform::Square,
+        Waveform::Triangle,
+        Waveform::Sawtooth,
+    ] {
+        let values = waveform.generate(100, 1.5, 2.5);
+
+        assert_eq!(values.len(), 100);
+        assert!(values.iter().all(|v| (1.0..=4.0).contains(v)));
+    }
+}
+
+#[test]
+fn waveform_shape() {
+    let sine = Waveform::Sine.generate(4, 1.0, 0.0);
+    assert_close(sine[0], 0.0);
+    assert_close(sine[1], 1.0);
+    assert_close(sine[3], -1.0);
+
+    let square = Waveform::Square.generate(4, 1.0, 0.0);
+    assert_eq!(square, vec![1.0, 1.0, -1.0, -1.0]);
+
+    let triangle = Waveform::Triangle.generate(4, 1.0, 0.0);
+    assert_eq!(triangle, vec![-1.0, 0.0, 1.0, 0.0]);
+
+    let sawtooth = Waveform::Sawtooth.generate(4, 1.0, 0.0);
+    assert_eq!(sawtooth, vec![-1.0, -0.5, 0.0, 0.5]);
+}
+
+#[test]
+fn samples_per_period() {
+    assert_eq!(Waveform::samples_for(1000.0, 10.0), 100);
+    assert_eq!(Waveform::samples_for(1000.0, 5000.0), 1);
+}
+
+#[test]
+fn stream_out_index_range() {
+    for index in [-1, 4, 7] {
+        assert!(matches!(
+            LJMLibrary::periodic_stream_out(1, index, "DAC0", 1000.0, &[0.0]),
+            Err(LJMError::InvalidStreamConfig(_))
+        ));
+        assert!(matches!(
+            LJMLibrary::write_aperiodic_stream_out(1, index, &[0.0]),
+            Err(LJMError::InvalidStreamConfig(_))
+        ));
+    }
+}